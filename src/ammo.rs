@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::time;
+use crate::settings;
+use crate::missile::MissileType;
+use crate::missile::missiles::KINDS;
+
+/// Heat of the gun when it overheats.
+pub const MAX_HEAT: f32 = 100.0;
+
+/// Limits of a missile kind that can't be fired forever.
+pub struct Ammo {
+    pub capacity: u16,
+    /// Time to get one round back while it isn't full, in milliseconds, `None` when it's
+    /// only refilled on a new generation.
+    pub reload_delay: Option<u16>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct AmmoPool {
+    pub rounds: u16,
+    /// When the last round came back, or when it stopped being full.
+    pub reload_tick: u64,
+}
+
+/// Rounds left of every missile kind with limited ammo, the others aren't in it.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Magazine {
+    pub pools: BTreeMap<MissileType, AmmoPool>,
+}
+
+impl Magazine {
+    pub fn full() -> Magazine {
+        let mut magazine = Magazine {
+            pools: BTreeMap::new(),
+        };
+        magazine.refill();
+
+        return magazine;
+    }
+
+    /// Rounds left and capacity of `missile_type`, `None` when it's unlimited.
+    pub fn rounds(&self, missile_type: MissileType) -> Option<(u16, u16)> {
+        let ammo = missile_type.kind().ammo()?;
+        let rounds = self.pools.get(&missile_type).map_or(0, |pool| pool.rounds);
+
+        return Some((rounds.min(ammo.capacity), ammo.capacity));
+    }
+
+    /// Takes a round of `missile_type`, false when it's empty.
+    pub fn take(&mut self, missile_type: MissileType, current_tick: u64) -> bool {
+        let Some(ammo) = missile_type.kind().ammo() else {
+            return true;
+        };
+        let pool = self.pools.entry(missile_type).or_insert(AmmoPool {
+            rounds: 0,
+            reload_tick: current_tick,
+        });
+        if pool.rounds == 0 {
+            return false;
+        }
+
+        pool.rounds = pool.rounds.min(ammo.capacity) - 1;
+        return true;
+    }
+
+    /// Gives a round back to the pools whose reload delay passed.
+    pub fn reload(&mut self, current_tick: u64) {
+        for (missile_type, pool) in self.pools.iter_mut() {
+            let Some(ammo) = missile_type.kind().ammo() else {
+                continue;
+            };
+            let Some(reload_delay) = ammo.reload_delay else {
+                continue;
+            };
+
+            if pool.rounds >= ammo.capacity {
+                pool.reload_tick = current_tick;
+            } else if current_tick >= pool.reload_tick + time::to_ticks(reload_delay) {
+                pool.rounds += 1;
+                pool.reload_tick = current_tick;
+            }
+        }
+    }
+
+    pub fn refill(&mut self) {
+        for kind in KINDS.iter() {
+            if let Some(ammo) = kind.ammo() {
+                self.pools.insert(kind.id(), AmmoPool {
+                    rounds: ammo.capacity,
+                    reload_tick: 0,
+                });
+            }
+        }
+    }
+}
+
+/// Heat of the gun, firing the kinds that warm it up is locked once it overheated
+/// until it cooled down to `overheat_recovery`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Heat {
+    pub value: f32,
+    pub overheated: bool,
+}
+
+impl Heat {
+    pub fn cold() -> Heat {
+        return Heat {
+            value: 0.0,
+            overheated: false,
+        };
+    }
+
+    pub fn warm_up(&mut self, heat: f32) {
+        self.value = (self.value + heat).min(MAX_HEAT);
+        if self.value >= MAX_HEAT {
+            self.overheated = true;
+        }
+    }
+
+    pub fn cool_down(&mut self) {
+        let settings = settings::get();
+        self.value = (self.value - settings.heat_cooling / settings.ticks_per_second as f32).max(0.0);
+        if self.overheated && self.value <= settings.overheat_recovery {
+            self.overheated = false;
+        }
+    }
+}
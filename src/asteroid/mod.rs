@@ -5,69 +5,108 @@ use crate::game::{Game, AsteroidRows, AsteroidRow};
 use crate::ui::{Ui};
 
 use rand::prelude::*;
+use rand_chacha::ChaCha12Rng;
+use serde::{Serialize, Deserialize};
 
-use physics::{Position, ChangingFactor};
+use physics::{Position, Vector, Body, SpatialGrid};
 
-#[derive(Clone, Copy, Debug)]
+/// Row and index of an asteroid in the `AsteroidRows`.
+pub type AsteroidKey = (usize, usize);
+
+/// Random positions tried before pushing a new asteroid past the ones it overlaps.
+const GENERATION_ATTEMPTS: u32 = 20;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Asteroid {
     pub position: Position,
+    pub previous_position: Position,
     pub row: usize,
     pub size: u8,
-    pub velocity: ChangingFactor,
-    pub direction: ChangingFactor,
-    pub acceleration: ChangingFactor,
+    pub body: Body,
 }
 
 impl Asteroid {
-    
+    pub fn rectangle(&self) -> Rectangle {
+        return Rectangle {
+            position: self.position,
+            size: Size::Square(Ui::to_pixels(self.size as u32)),
+        };
+    }
+
+    /// The area covered during the last tick, from `previous_position` to `position`.
+    pub fn swept_rectangle(&self) -> Rectangle {
+        let previous_rectangle = Rectangle {
+            position: self.previous_position,
+            size: Size::Square(Ui::to_pixels(self.size as u32)),
+        };
+
+        return previous_rectangle.union(&self.rectangle());
+    }
+
+    /// Broadphase of the asteroids, holding what they covered during the last tick.
+    pub fn grid(asteroids: &AsteroidRows) -> SpatialGrid<AsteroidKey> {
+        let mut grid = SpatialGrid::new(settings::get().collision_grid_cell_size);
+
+        for (row_i, row) in asteroids.iter().enumerate() {
+            for (asteroid_i, asteroid) in row.iter().enumerate() {
+                grid.insert((row_i, asteroid_i), &asteroid.swept_rectangle());
+            }
+        }
+
+        return grid;
+    }
 
     pub fn new(
-        rng: &mut rand::prelude::ThreadRng,
-        existing_asteroids: Option<&AsteroidRows>,
+        rng: &mut ChaCha12Rng,
+        existing_asteroids: Option<(&AsteroidRows, &SpatialGrid<AsteroidKey>)>,
         row: Option<usize>,
     ) -> Asteroid {
-            let row = if let Some(set_row) = row { set_row } else { rng.gen_range(0..settings::ASTEROIDS_ROWS) };
-            let mut asteroid_position;
-            let mut size;
+            let settings = settings::get();
+            let row = if let Some(set_row) = row { set_row } else { rng.gen_range(0..settings.asteroids_rows()) };
+            let mut asteroid_position = Position { x: 0.0, y: 0.0 };
+            let mut size = 0;
+            let mut attempts = 0;
             'generation_loop: loop {
-                size = rng.gen_range(
-                    settings::MIN_ASTEROIDS_SIZE..settings::MAX_ASTEROIDS_SIZE
-                );
-        
-                let minimum_x_position = (settings::WINDOW_WIDTH + Ui::to_pixels(size as u32)) as i32;
-        
-                asteroid_position = Position {
-                    x: rng.gen_range(
-                        minimum_x_position
-                        ..
-                        minimum_x_position + settings::ASTEROIDS_MARGIN.x
-                    ),
-                    y: Game::get_centered_row_y_position(row) as i32,
-                };
+                if attempts < GENERATION_ATTEMPTS {
+                    size = rng.gen_range(
+                        settings.min_asteroids_size..settings.max_asteroids_size
+                    );
+            
+                    let minimum_x_position = (settings.window_width + Ui::to_pixels(size as u32)) as f32;
+            
+                    asteroid_position = Position {
+                        x: rng.gen_range(
+                            minimum_x_position
+                            ..
+                            minimum_x_position + settings.asteroids_margin.x
+                        ),
+                        y: Game::get_centered_row_y_position(row) as f32,
+                    };
+                }
+                attempts += 1;
     
-                if settings::ALLOW_INSIDE_GENERATION {
+                if settings.allow_inside_generation {
                     break 'generation_loop;
                 }
         
                 let mut inside = false;
-                if let Some(existing_asteroids) = existing_asteroids {
-                    for row in existing_asteroids.iter() {
-                        for existing_asteroid in row.iter() {
-                            let outside_rectangle = Rectangle {
-                                position: existing_asteroid.position,
-                                size: Size::Square(
-                                    Ui::to_pixels(existing_asteroid.size as u32)
-                                ),
-                            };
-                            let inside_rectangle = Rectangle {
-                                position: asteroid_position,
-                                size: Size::Square(Ui::to_pixels(size as u32)),
-                            };
-                
-                            if inside_rectangle.over(outside_rectangle) {
-                                inside = true;
-                                break; 
+                if let Some((existing_asteroids, grid)) = existing_asteroids {
+                    let inside_rectangle = Rectangle {
+                        position: asteroid_position,
+                        size: Size::Square(Ui::to_pixels(size as u32)),
+                    };
+
+                    for (row_i, asteroid_i) in grid.query(&inside_rectangle) {
+                        let outside_rectangle = existing_asteroids[row_i][asteroid_i].rectangle();
+            
+                        if inside_rectangle.intersects(&outside_rectangle) {
+                            // A crowded row may have no free spot left in the margin
+                            if attempts >= GENERATION_ATTEMPTS {
+                                asteroid_position.x = outside_rectangle.get_corners().bottom_right.x
+                                    + Ui::to_pixels(size as u32) as f32 / 2.0 + 1.0;
                             }
+                            inside = true;
+                            break;
                         }
                     }
                 }
@@ -79,45 +118,30 @@ impl Asteroid {
     
             let generated_asteroid = Asteroid {
                 position: asteroid_position,
+                previous_position: asteroid_position,
                 size,
                 row,
-                velocity: ChangingFactor {
-                    x: rng.gen_range(
-                        settings::MIN_ASTEROIDS_SPEED..settings::MAX_ASTEROIDS_SPEED
-                    ) as f32,
-                    y: 0.0,
-                },
-                direction: ChangingFactor {
-                    x: -1_f32,
-                    y: 0_f32,
-                },
-                acceleration: ChangingFactor {
-                    x: 0.04,
-                    y: 0_f32,
-                }
+                // Drifts at a constant speed, bigger asteroids are heavier
+                body: Body::new(
+                    Vector::new(
+                        -(rng.gen_range(settings.min_asteroids_speed..settings.max_asteroids_speed) as f32),
+                        0.0,
+                    ),
+                    size as f32,
+                    Vector::ZERO,
+                ),
             };
     
             return generated_asteroid;
     }
 
     pub fn update_asteroid_position(&mut self) {
-        self.position = physics::next_position(
-            Rectangle {
-                position: self.position,
-                size: Size::Square(self.size as u32),
-            },
-            self.velocity,
-            vec![
-                physics::Force {
-                    direction: self.direction,
-                    acceleration: self.acceleration,
-                }
-            ]
-        );
+        self.previous_position = self.position;
+        self.position = self.body.integrate(self.position);
     }
 
     pub fn is_used(&self) -> bool {
-        self.position.x > 0 && self.size > 0
+        self.position.x > 0.0 && self.size > 0
     }
 
     pub fn update_asteroids_positions(asteroids: &mut AsteroidRows) {
@@ -144,6 +168,7 @@ impl Asteroid {
     }
 
     pub fn appearing_asteroids(asteroids: &AsteroidRows) -> i32 {
+        let settings = settings::get();
         let mut appearing_asteroids = 0;
 
         for row in asteroids.iter() {
@@ -153,27 +178,15 @@ impl Asteroid {
                     size: Size::Square(Ui::to_pixels(asteroid.size as u32)),
                 }.get_corners();
 
-                if (corners.top_left.x as i64) < settings::WINDOW_WIDTH as i64
-                && corners.top_left.x as i64 > settings::WINDOW_WIDTH as i64 - settings::GENERATE_NEW_ASTEROID_AFTER as i64 {
-                    let next_position = physics::next_position(
-                        Rectangle {
-                            position: asteroid.position,
-                            size: Size::Square(asteroid.size as u32),
-                        },
-                        asteroid.velocity,
-                        vec![
-                            physics::Force {
-                                direction: asteroid.direction,
-                                acceleration: asteroid.acceleration,
-                            }
-                        ]
-                    );
+                if (corners.top_left.x as i64) < settings.window_width as i64
+                && corners.top_left.x as i64 > settings.window_width as i64 - settings.generate_new_asteroid_after as i64 {
+                    let next_position = asteroid.body.next_position(asteroid.position);
                     let corners = Rectangle {
                         position: next_position,
                         size: Size::Square(Ui::to_pixels(asteroid.size as u32)),
                     }.get_corners();
                     
-                    if corners.top_left.x as i64 <= settings::WINDOW_WIDTH as i64 - settings::GENERATE_NEW_ASTEROID_AFTER as i64 {
+                    if corners.top_left.x as i64 <= settings.window_width as i64 - settings.generate_new_asteroid_after as i64 {
                         appearing_asteroids += 1;
                     }
                 }
@@ -183,9 +196,8 @@ impl Asteroid {
         return appearing_asteroids;
     }
 
-    pub fn initialize_asteroids(rows: usize, min_asteroids_per_row: u8, max_asteroids_per_row: u8) -> AsteroidRows {
+    pub fn initialize_asteroids(rng: &mut ChaCha12Rng, rows: usize, min_asteroids_per_row: u8, max_asteroids_per_row: u8) -> AsteroidRows {
         let mut asteroids = vec![vec![]; rows];
-        let mut rng = rand::thread_rng();
         for row_i in 0..rows {
             let range;
             if min_asteroids_per_row >= max_asteroids_per_row {
@@ -195,7 +207,7 @@ impl Asteroid {
             }
 
             for _ in 0..range {
-                let generated_asteroid = Asteroid::new(&mut rng, Some(&vec![vec![]; rows]), Some(row_i));
+                let generated_asteroid = Asteroid::new(rng, None, Some(row_i));
                 asteroids[row_i].push(generated_asteroid);
             }
         }
@@ -0,0 +1,113 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+use crate::settings;
+use crate::physics::{Position, SpatialGrid};
+use crate::rectangle::{Rectangle, Size, RectangleSize};
+
+/// Amounts of asteroids measured, with a missile for every 4 asteroids.
+const ASTEROIDS_COUNTS: [usize; 5] = [100, 500, 1_000, 5_000, 10_000];
+const RUNS: u32 = 5;
+/// Window area given to every asteroid, about the density of a real game.
+const AREA_PER_ASTEROID: i64 = 20_000;
+
+pub struct Measure {
+    pub asteroids: usize,
+    pub missiles: usize,
+    pub hits: usize,
+    pub brute_force: Duration,
+    pub grid: Duration,
+}
+
+impl fmt::Display for Measure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{asteroids:>9} {missiles:>9} {hits:>7} {brute_force:>14.3?} {grid:>14.3?} {speedup:>8.1}x",
+            asteroids=self.asteroids,
+            missiles=self.missiles,
+            hits=self.hits,
+            brute_force=self.brute_force,
+            grid=self.grid,
+            speedup=self.brute_force.as_secs_f64() / self.grid.as_secs_f64(),
+        )
+    }
+}
+
+fn random_rectangles(rng: &mut StdRng, count: usize, side: f32, width: u32, height: u32) -> Vec<Rectangle> {
+    return (0..count)
+        .map(|_| Rectangle {
+            position: Position {
+                x: rng.gen_range(0.0..side),
+                y: rng.gen_range(0.0..side),
+            },
+            size: Size::Rectangle(RectangleSize {
+                width,
+                height,
+            }),
+        })
+        .collect();
+}
+
+/// Times the missile against asteroid queries of a tick, checking every pair against
+/// going through the broadphase (building it included).
+pub fn measure(rng: &mut StdRng, asteroids_count: usize) -> Measure {
+    let settings = settings::get();
+    let side = ((asteroids_count as i64 * AREA_PER_ASTEROID) as f64).sqrt() as f32;
+    let asteroid_side = settings.max_asteroids_size as u32 * settings.pixels_multiplier_factor;
+
+    let asteroids = random_rectangles(rng, asteroids_count, side, asteroid_side, asteroid_side);
+    let missiles = random_rectangles(rng, asteroids_count / 4, side, settings.missile_width * 2, settings.missile_height);
+
+    let mut brute_force_hits = 0;
+    let brute_force_start = Instant::now();
+    for _ in 0..RUNS {
+        brute_force_hits = 0;
+        for missile in missiles.iter() {
+            brute_force_hits += asteroids.iter().filter(|asteroid| missile.intersects(asteroid)).count();
+        }
+    }
+    let brute_force = brute_force_start.elapsed() / RUNS;
+
+    let mut grid_hits = 0;
+    let grid_start = Instant::now();
+    for _ in 0..RUNS {
+        let mut grid = SpatialGrid::new(settings.collision_grid_cell_size);
+        for (asteroid_i, asteroid) in asteroids.iter().enumerate() {
+            grid.insert(asteroid_i, asteroid);
+        }
+
+        grid_hits = 0;
+        for missile in missiles.iter() {
+            grid_hits += grid
+                .query(missile)
+                .into_iter()
+                .filter(|&asteroid_i| missile.intersects(&asteroids[asteroid_i]))
+                .count();
+        }
+    }
+    let grid = grid_start.elapsed() / RUNS;
+
+    assert_eq!(brute_force_hits, grid_hits, "The broadphase missed some collisions");
+
+    return Measure {
+        asteroids: asteroids_count,
+        missiles: missiles.len(),
+        hits: grid_hits,
+        brute_force,
+        grid,
+    };
+}
+
+pub fn run() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    println!("Missile against asteroid collisions, average of {} runs", RUNS);
+    println!("{:>9} {:>9} {:>7} {:>14} {:>14} {:>9}", "Asteroids", "Missiles", "Hits", "Brute force", "Grid", "Speedup");
+    for asteroids_count in ASTEROIDS_COUNTS {
+        println!("{}", measure(&mut rng, asteroids_count));
+    }
+}
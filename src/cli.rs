@@ -0,0 +1,148 @@
+use std::path::Path;
+
+use clap::{CommandFactory, Parser};
+use clap::builder::PossibleValuesParser;
+use clap::builder::TypedValueParser;
+use clap::error::ErrorKind;
+use log::LevelFilter;
+
+use crate::settings::{self, Settings};
+
+/// A side-scrolling spaceship shooter.
+///
+/// Every option overrides the matching value of the settings file.
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Window width, in pixels
+    #[arg(long, value_name = "PIXELS")]
+    pub width: Option<u32>,
+
+    /// Window height, in pixels
+    #[arg(long, value_name = "PIXELS")]
+    pub height: Option<u32>,
+
+    /// Open the window in fullscreen
+    #[arg(long, conflicts_with = "headless")]
+    pub fullscreen: bool,
+
+    /// Maximum frames per second of the window
+    #[arg(long, conflicts_with = "headless")]
+    pub fps: Option<u32>,
+
+    /// Seed of the run, a random one is used when missing
+    #[arg(long, conflicts_with = "replay")]
+    pub seed: Option<u64>,
+
+    /// Settings file to load
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<String>,
+
+    /// Generation the game starts at
+    #[arg(long, value_name = "GENERATION")]
+    pub generation: Option<u32>,
+
+    /// Turn the debug mode on
+    #[arg(long, overrides_with = "no_debug")]
+    pub debug: bool,
+
+    /// Turn the debug mode off
+    #[arg(long, overrides_with = "debug")]
+    pub no_debug: bool,
+
+    /// Run the simulation without a window and print a summary
+    #[arg(long)]
+    pub headless: bool,
+
+    /// Measure the collision broadphase against checking every pair and exit
+    #[arg(long, conflicts_with_all = ["headless", "replay", "record", "fullscreen", "fps"])]
+    pub benchmark: bool,
+
+    /// Amount of ticks to simulate in headless mode
+    #[arg(long, requires = "headless")]
+    pub ticks: Option<u64>,
+
+    /// Replay file to feed as inputs in headless mode
+    #[arg(long, value_name = "PATH", requires = "headless", conflicts_with = "replay")]
+    pub script: Option<String>,
+
+    /// Replay file to play back
+    #[arg(long, value_name = "PATH")]
+    pub replay: Option<String>,
+
+    /// Save the inputs of the run as a replay file
+    #[arg(long, value_name = "PATH", conflicts_with = "replay")]
+    pub record: Option<String>,
+
+    /// Most verbose messages to print
+    #[arg(
+        long,
+        value_name = "LEVEL",
+        default_value = "warn",
+        value_parser = PossibleValuesParser::new(["off", "error", "warn", "info", "debug", "trace"])
+            .map(|level| level.parse::<LevelFilter>().unwrap()),
+    )]
+    pub log_level: LevelFilter,
+}
+
+impl Cli {
+    /// Parses the arguments of the process, printing the help on invalid ones.
+    pub fn parse_args() -> Cli {
+        return Cli::try_parse().unwrap_or_else(|error| {
+            if !matches!(error.kind(), ErrorKind::DisplayHelp | ErrorKind::DisplayVersion) {
+                Cli::command().print_help().unwrap();
+                eprintln!();
+            }
+            error.exit();
+        });
+    }
+
+    pub fn debug(&self) -> Option<bool> {
+        if self.debug {
+            return Some(true);
+        }
+        if self.no_debug {
+            return Some(false);
+        }
+
+        return None;
+    }
+
+    pub fn config_path(&self) -> &str {
+        return self.config.as_deref().unwrap_or(settings::SETTINGS_FILE_PATH);
+    }
+
+    /// Loads the settings file and applies the options on top of it.
+    ///
+    /// Only an explicit `--config` has to exist, the default one falls back to the default settings.
+    pub fn settings(&self) -> Result<Settings, String> {
+        let path = self.config_path();
+        if self.config.is_some() && !Path::new(path).exists() {
+            return Err(format!("Settings file not found: {}", path));
+        }
+        let mut settings = Settings::load(path)?;
+
+        if let Some(width) = self.width {
+            settings.window_width = width;
+        }
+        if let Some(height) = self.height {
+            settings.window_height = height;
+        }
+        if self.fullscreen {
+            settings.fullscreen = true;
+        }
+        if let Some(fps) = self.fps {
+            settings.fps = fps;
+        }
+        if let Some(generation) = self.generation {
+            settings.starting_generation = generation;
+        }
+        if let Some(debug) = self.debug() {
+            settings.debug = debug;
+        }
+
+        settings.validate().map_err(|error| format!("Invalid options:\n{}", error))?;
+
+        return Ok(settings);
+    }
+}
@@ -1,48 +1,89 @@
-use sdl2::event::Event;
-use sdl2::mouse::MouseButton;
-use sdl2::keyboard::Keycode;
-use std::sync::mpsc::{Sender, Receiver};
+use std::sync::mpsc::Sender;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Serialize, Deserialize};
 
 use crate::time;
 use crate::settings;
 use crate::physics;
-use crate::asteroid::{Asteroid};
+use crate::asteroid::{Asteroid, AsteroidKey};
 use crate::missile;
-use crate::helper::{G2UMessage, U2GMessage};
+use crate::laser::{Laser, Beam};
+use crate::ammo::{Magazine, Heat};
+use crate::helper::{G2UMessage, U2GMessage, InputSource};
 use crate::rectangle::{Rectangle, Size, RectangleSize};
 use crate::ui::Ui;
+use crate::snapshot::Snapshot;
+use crate::highscore::{HighScore, HighScoreTable};
+use crate::scene::{Scene, SceneStack, MenuItem};
 
-use missile::{Missile, MissileType};
-use physics::{Position};
+use missile::{Missile, MissileType, MAX_WEAPON_LEVEL};
+use missile::missiles::Normal;
+use physics::{Position, ChangingFactor, SpatialGrid};
 
 
 pub type AsteroidRow = Vec<Asteroid>;
 pub type AsteroidRows = Vec<AsteroidRow>;
 
-#[derive(Clone)]
+/// How long an explosion stays in `Game::explosions` to be drawn in debug mode, in milliseconds.
+const EXPLOSION_DEBUG_DELAY: u16 = 500;
+
+/// A blast of `Game::explode`, kept for a while to draw its radius.
+#[derive(Clone, Copy, Debug)]
+pub struct Explosion {
+    pub center: Position,
+    pub radius: f32,
+    pub tick: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ShootingInfo {
-    pub last_shot_time: u128,
-    pub delay_to_next_shot: u128,
+    pub last_shot_tick: u64,
+    pub delay_to_next_shot: u64,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Score {
+    pub points: u64,
+    pub multiplier: u32,
+    pub combo_hits: u32,
+    pub last_hit_tick: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Spaceship {
     pub position: Position,
+    /// Where it was on the last crash check.
+    pub previous_position: Position,
     pub life: u8,
     pub shooting: bool,
     pub missile_type: MissileType,
+    /// From 1 to `MAX_WEAPON_LEVEL`, raised every `points_per_weapon_level` points.
+    pub weapon_level: u8,
+    /// Crashes are ignored until this tick.
+    pub invulnerable_until_tick: u64,
+    /// Speed it was pushed away at by the last crash, in pixels per tick.
+    pub knockback: ChangingFactor,
+    pub laser: Laser,
+    pub magazine: Magazine,
+    pub heat: Heat,
 }
 
-#[derive(Clone)]
+impl Spaceship {
+    pub fn is_invulnerable(&self, current_tick: u64) -> bool {
+        return current_tick < self.invulnerable_until_tick;
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum State {
     Running,
-    Paused,
     Closed,
     Died,
-    NextGen(u128),
+    NextGen(u64),
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct DebugOptions {
     pub generation_line: bool,
     pub rows: bool,
@@ -51,55 +92,102 @@ pub struct DebugOptions {
     pub asteroid_generation: bool,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Game {
     pub spaceship: Spaceship,
     pub asteroids: AsteroidRows,
     pub missiles: Vec<Missile>,
     pub shooting_info: ShootingInfo,
+    pub score: Score,
     pub state: State,
+    pub scenes: SceneStack,
     pub debug_options: DebugOptions,
     pub debugging: bool,
+    pub seed: u64,
+    /// Saved along with the game, so a loaded snapshot carries on with the same sequence.
+    pub rng: ChaCha12Rng,
+    pub current_tick: u64,
+    pub generation: u32,
+    /// Only loaded and saved by the windowed game loop, so headless runs never touch it.
+    #[serde(skip)]
+    pub high_scores: HighScoreTable,
+    #[serde(skip)]
+    pub high_score_rank: Option<usize>,
+    /// Why the last edit of the settings file was rejected, shown until a valid one is saved.
+    #[serde(skip)]
+    pub settings_error: Option<String>,
+    /// Recent explosions, only drawn in debug mode.
+    #[serde(skip)]
+    pub explosions: Vec<Explosion>,
 }
 
 impl Game {
     pub fn new() -> Game {
+        return Game::with_seed(rand::random());
+    }
+
+    pub fn with_seed(seed: u64) -> Game {
+        let settings = settings::get();
+        let mut rng = ChaCha12Rng::seed_from_u64(seed);
+
         return Game {
             spaceship: Spaceship {
-                position: Position {
-                    x: settings::INITIAL_SPACESHIP_POSITION.x,
-                    y: settings::INITIAL_SPACESHIP_POSITION.y,
-                },
-                life: settings::SPACESHIP_LIFE,
+                position: settings.initial_spaceship_position(),
+                previous_position: settings.initial_spaceship_position(),
+                life: settings.spaceship_life,
                 shooting: false,
-                missile_type: MissileType::Normal,
+                missile_type: Normal::ID,
+                weapon_level: 1,
+                invulnerable_until_tick: 0,
+                knockback: ChangingFactor::ZERO,
+                laser: Laser::charged(),
+                magazine: Magazine::full(),
+                heat: Heat::cold(),
             },
             shooting_info: ShootingInfo {
-                last_shot_time: time::now(),
+                last_shot_tick: 0,
                 delay_to_next_shot: 0,
             },
+            score: Score {
+                points: 0,
+                multiplier: 1,
+                combo_hits: 0,
+                last_hit_tick: 0,
+            },
             asteroids: Asteroid::initialize_asteroids(
-                settings::ASTEROIDS_ROWS,
-                settings::MIN_GENERATED_ASTEROIDS,
-                settings::MAX_GENERATED_ASTEROIDS,
+                &mut rng,
+                settings.asteroids_rows(),
+                settings.min_generated_asteroids,
+                settings.max_generated_asteroids,
             ),
             missiles: vec![],
             state: State::Running,
-            debug_options: settings::DEFAULT_DEBUG_OPTIONS,
-            debugging: settings::DEBUG,
+            scenes: SceneStack::new(Scene::InGame),
+            debug_options: settings.default_debug_options.clone(),
+            debugging: settings.debug,
+            seed,
+            rng,
+            current_tick: 0,
+            generation: settings.starting_generation,
+            high_scores: HighScoreTable::default(),
+            high_score_rank: None,
+            settings_error: None,
+            explosions: vec![],
         };
     }
 
     pub fn get_centered_row_y_position(row: usize) -> u32 {
-        let y_position = (settings::ASTEROIDS_ROWS_HEIGHT as u32 * row as u32) + settings::ASTEROIDS_ROWS_HEIGHT as u32 / 2;
+        let settings = settings::get();
+        let y_position = (settings.asteroids_rows_height() as u32 * row as u32) + settings.asteroids_rows_height() as u32 / 2;
         return y_position;
     }
 
-    pub fn get_row_by_y_position(y_position: i32) -> usize {
-        return (y_position as i64 / settings::ASTEROIDS_ROWS_HEIGHT as i64) as usize; 
+    pub fn get_row_by_y_position(y_position: f32) -> usize {
+        let settings = settings::get();
+        return (y_position / settings.asteroids_rows_height() as f32) as usize; 
     }
 
-    fn asteroids_generation(&mut self) {
+    fn asteroids_generation(&mut self, asteroid_grid: &mut SpatialGrid<AsteroidKey>) {
         if self.debugging {
             if !self.debug_options.asteroid_generation {
                 return;
@@ -107,61 +195,125 @@ impl Game {
         }
         let appearing_asteroids = Asteroid::appearing_asteroids(&self.asteroids);
 
-        let mut rng = rand::thread_rng();
         for _ in 0..appearing_asteroids {
-            let generated_asteroid = Asteroid::new(&mut rng, Some(&self.asteroids), None);
-            self.asteroids[generated_asteroid.row].push(generated_asteroid);
+            let generated_asteroid = Asteroid::new(&mut self.rng, Some((&self.asteroids, asteroid_grid)), None);
+            let row = generated_asteroid.row;
+            asteroid_grid.insert((row, self.asteroids[row].len()), &generated_asteroid.rectangle());
+            self.asteroids[row].push(generated_asteroid);
         }
     }
 
-    fn check_spaceship_crash(&mut self) {
+    fn get_spaceship_rectangle(position: Position) -> Rectangle {
+        let settings = settings::get();
+        return Rectangle {
+            position: Position {
+                x: position.x,
+                y: position.y + settings.spaceship_height as f32 / 2.0,
+            },
+            size: Size::Rectangle(RectangleSize {
+                width: settings.spaceship_width,
+                height: settings.spaceship_height,
+            }),
+        };
+    }
+
+    /// Sweeps every asteroid along its move of the last tick, relatively to the spaceship's own
+    /// move, and crashes the first one that touched the spaceship.
+    ///
+    /// The asteroid is destroyed, the damage grows with its size and speed, and the spaceship is
+    /// knocked back and ignores crashes for `invulnerability_delay` milliseconds.
+    fn check_spaceship_crash(&mut self, asteroid_grid: &SpatialGrid<AsteroidKey>) {
+        let settings = settings::get();
+        let spaceship_displacement = self.spaceship.position - self.spaceship.previous_position;
+        let spaceship_rectangle = Game::get_spaceship_rectangle(self.spaceship.previous_position);
+        let swept_spaceship_rectangle = spaceship_rectangle.union(&Game::get_spaceship_rectangle(self.spaceship.position));
+        self.spaceship.previous_position = self.spaceship.position;
+
         if self.debugging && self.debug_options.invincible {
             return;
         }
-        for row in self.asteroids.iter() {
-            for asteroid in row.iter() {
-                let new_spaceship_life = self.spaceship.life as i16 - asteroid.size as i16;
-                let next_pos = physics::next_position(
-                    Rectangle {
-                        position: asteroid.position,
-                        size: Size::Square(Ui::to_pixels(asteroid.size as u32)),
-                    },
-                    asteroid.velocity,
-                    vec![
-                        physics::Force {
-                            direction: asteroid.direction,
-                            acceleration: asteroid.acceleration,
-                        }
-                    ]
-                );
-
-                if next_pos.x < self.spaceship.position.x && asteroid.position.x > self.spaceship.position.x && new_spaceship_life > 0 {
-                    if self.spaceship.life as i32 - (asteroid.size as i32) < 0 {
-                        self.spaceship.life = 0;
-                    } else {
-                        self.spaceship.life -= asteroid.size;
-                    }
-                }else if new_spaceship_life == 0 {
-                    self.state = State::Died;
+        if self.spaceship.is_invulnerable(self.current_tick) {
+            return;
+        }
+
+        let mut first_crash: Option<(f32, AsteroidKey, Position)> = None;
+        for (row_i, asteroid_i) in asteroid_grid.query(&swept_spaceship_rectangle) {
+            let asteroid = self.asteroids[row_i][asteroid_i];
+            if asteroid.size == 0 {
+                continue;
+            }
+
+            let asteroid_rectangle = Rectangle {
+                position: asteroid.previous_position,
+                size: Size::Square(Ui::to_pixels(asteroid.size as u32)),
+            };
+            let relative_displacement = (asteroid.position - asteroid.previous_position) - spaceship_displacement;
+
+            if let Some(time) = physics::sweep(&asteroid_rectangle, relative_displacement, &spaceship_rectangle) {
+                if first_crash.is_none_or(|(first_time, ..)| time < first_time) {
+                    first_crash = Some((time, (row_i, asteroid_i), relative_displacement));
                 }
             }
         }
+
+        let Some((_, (row_i, asteroid_i), relative_displacement)) = first_crash else {
+            return;
+        };
+        let asteroid = &mut self.asteroids[row_i][asteroid_i];
+
+        let speed = relative_displacement.length();
+        let damage = asteroid.size as f32 * settings.crash_damage_per_size + speed * settings.crash_damage_per_speed;
+        self.spaceship.life = self.spaceship.life.saturating_sub(damage.round().clamp(1.0, u8::MAX as f32) as u8);
+
+        // Pushed away from the center of the asteroid
+        let spaceship_center = Game::get_spaceship_rectangle(self.spaceship.position).position;
+        let away = (spaceship_center - asteroid.position).normalized();
+        self.spaceship.knockback = away * settings.crash_knockback;
+
+        asteroid.size = 0;
+        self.spaceship.invulnerable_until_tick = self.current_tick + time::to_ticks(settings.invulnerability_delay);
+
+        if self.spaceship.life == 0 {
+            self.die();
+        }
+    }
+
+    /// Moves the spaceship by its knockback, which slows down every tick, and brings it back
+    /// to its initial x position once it's slow enough.
+    fn update_knockback(&mut self) {
+        let settings = settings::get();
+        let spaceship = &mut self.spaceship;
+
+        spaceship.position += spaceship.knockback;
+        spaceship.knockback = spaceship.knockback * settings.knockback_damping;
+
+        if spaceship.knockback.x.abs() < 1.0 {
+            spaceship.position.x += (settings.initial_spaceship_x - spaceship.position.x).clamp(-2.0, 2.0);
+        }
+
+        spaceship.position.x = spaceship.position.x.max(settings.spaceship_width as f32 / 2.0);
+        spaceship.position.y = spaceship.position.y
+            .clamp(0.0, (settings.window_height - settings.spaceship_height) as f32);
     }
 
     fn shot(&mut self) {
-        if self.spaceship.shooting && time::now() >= self.shooting_info.last_shot_time + self.shooting_info.delay_to_next_shot{
-            let missile = Missile {
-                active: true,
-                velocity: Missile::get_types_data(self.spaceship.missile_type).initial_velocity,
-                position: Position {
-                    x: self.spaceship.position.x + settings::SPACESHIP_WIDTH as i32 / 2,
-                    y: self.spaceship.position.y + settings::SPACESHIP_HEIGHT as i32 / 2,
-                },
-                missile_type: self.spaceship.missile_type,
-                direction: Missile::get_types_data(self.spaceship.missile_type).direction,
-                acceleration: Missile::get_types_data(self.spaceship.missile_type).acceleration,
-                collision_handler: Missile::get_types_handler(self.spaceship.missile_type),
+        let settings = settings::get();
+        if self.spaceship.shooting && self.current_tick >= self.shooting_info.last_shot_tick + self.shooting_info.delay_to_next_shot {
+            let position = Position {
+                x: self.spaceship.position.x + settings.spaceship_width as f32 / 2.0,
+                y: self.spaceship.position.y + settings.spaceship_height as f32 / 2.0,
             };
+            let kind = self.spaceship.missile_type.kind();
+            if kind.heat() > 0.0 && self.spaceship.heat.overheated {
+                return;
+            }
+            if !self.spaceship.magazine.take(self.spaceship.missile_type, self.current_tick) {
+                return;
+            }
+
+            let data = kind.data(self.spaceship.weapon_level);
+            let mut missile = Missile::new(self.spaceship.missile_type, &data, position, self.current_tick);
+            kind.on_spawn(self, &mut missile);
             
             if self.missiles.len() > 0 && self.missiles[0].active == false {
                 self.missiles[0] = missile;
@@ -169,56 +321,243 @@ impl Game {
                 self.missiles.push(missile);
             }
 
-            self.shooting_info.last_shot_time = time::now();
-            self.shooting_info.delay_to_next_shot = Missile::get_types_data(self.spaceship.missile_type).delay;
+            self.shooting_info.last_shot_tick = self.current_tick;
+            self.shooting_info.delay_to_next_shot = data.delay;
+            self.spaceship.heat.warm_up(kind.heat());
+        }
+    }
+
+    /// Cools the gun down and reloads the weapons with limited ammo.
+    fn update_weapons(&mut self) {
+        self.spaceship.heat.cool_down();
+        self.spaceship.magazine.reload(self.current_tick);
+    }
+
+    /// Fires the laser from the spaceship to the first asteroid in its row, which loses
+    /// `laser_damage` sizes per second, or recharges it while it's released.
+    fn update_laser(&mut self, asteroid_grid: &SpatialGrid<AsteroidKey>) {
+        let settings = settings::get();
+        if !self.spaceship.laser.drain() {
+            self.spaceship.laser.recharge();
+            self.spaceship.laser.beam = None;
+            self.spaceship.laser.damage = 0.0;
+            return;
+        }
+
+        let start = Position {
+            x: self.spaceship.position.x + settings.spaceship_width as f32 / 2.0,
+            y: self.spaceship.position.y + settings.spaceship_height as f32 / 2.0,
+        };
+        let full_beam = Beam {
+            start,
+            end: Position {
+                x: settings.window_width as f32,
+                y: start.y,
+            },
+        };
+        let full_beam_rectangle = full_beam.rectangle();
+
+        let mut first_hit: Option<(f32, AsteroidKey)> = None;
+        for (row, asteroid_i) in asteroid_grid.query(&full_beam_rectangle) {
+            let asteroid = self.asteroids[row][asteroid_i];
+            if asteroid.size == 0 {
+                continue;
+            }
+
+            let Some(overlap) = asteroid.rectangle().intersection(&full_beam_rectangle) else {
+                continue;
+            };
+
+            let hit_x = overlap.get_corners().top_left.x;
+            if first_hit.is_none_or(|(first_hit_x, _)| hit_x < first_hit_x) {
+                first_hit = Some((hit_x, (row, asteroid_i)));
+            }
+        }
+
+        let Some((hit_x, (row, asteroid_i))) = first_hit else {
+            self.spaceship.laser.beam = Some(full_beam);
+            self.spaceship.laser.damage = 0.0;
+            return;
+        };
+
+        let laser = &mut self.spaceship.laser;
+        laser.beam = Some(Beam {
+            start,
+            end: Position {
+                x: hit_x,
+                y: start.y,
+            },
+        });
+        laser.damage += settings.laser_damage / settings.ticks_per_second as f32;
+
+        if laser.damage >= 1.0 {
+            laser.damage -= 1.0;
+            let asteroid = &mut self.asteroids[row][asteroid_i];
+            asteroid.size -= 1;
+            let size = asteroid.size;
+            self.register_hit(size + 1, size == 0);
         }
     }
 
-    fn check_missile_collision(&mut self) {
+    /// Sweeps every missile along its path of the last tick, so fast missiles can't skip over
+    /// an asteroid, and hits the asteroid it touched first.
+    fn check_missile_collision(&mut self, asteroid_grid: &SpatialGrid<AsteroidKey>) {
+        let settings = settings::get();
         let mut cloned_missiles = self.missiles.clone();
         for (missile_i, missile) in cloned_missiles.iter_mut().enumerate() {
             if !missile.active { continue; }
-
-            let missiles_row = Game::get_row_by_y_position(missile.position.y);
-            let inside_rectangle = Rectangle {
-                position: missile.position,
-                size: Size::Rectangle(RectangleSize {
-                    width: settings::MISSILE_WIDTH,
-                    height: settings::MISSILE_HEIGHT,
-                }),
+            // Still going through the asteroid it pierced
+            if self.current_tick < missile.passing_until_tick { continue; }
+
+            let missile_size = Size::Rectangle(RectangleSize {
+                width: settings.missile_width,
+                height: settings.missile_height,
+            });
+            let missile_rectangle = Rectangle {
+                position: missile.previous_position,
+                size: missile_size.clone(),
             };
-            if missiles_row >= self.asteroids.len() {
-                continue;
-            }
+            let swept_rectangle = missile_rectangle.union(&Rectangle {
+                position: missile.position,
+                size: missile_size,
+            });
+
+            let mut first_hit: Option<(f32, usize, usize)> = None;
+            for (row, asteroid_i) in asteroid_grid.query(&swept_rectangle) {
+                let asteroid = self.asteroids[row][asteroid_i];
+                // Destroyed by another missile this tick, it's unloaded at the end of it
+                if asteroid.size == 0 {
+                    continue;
+                }
 
-            let mut cloned_asteroids = self.asteroids[missiles_row].clone();
-            
-            for (asteroid_i, asteroid) in cloned_asteroids.iter_mut().enumerate() {
-                let outside_rectangle = Rectangle {
-                    position: asteroid.position,
+                let asteroid_rectangle = Rectangle {
+                    position: asteroid.previous_position,
                     size: Size::Square(Ui::to_pixels(asteroid.size as u32)),
                 };
-                
-                if inside_rectangle.over(outside_rectangle) {
-                    (missile.collision_handler)(self, missile, asteroid);
-                    self.asteroids[missiles_row][asteroid_i] = *asteroid;
-                    self.missiles[missile_i] = missile.clone();
+                let relative_displacement = (missile.position - missile.previous_position)
+                    - (asteroid.position - asteroid.previous_position);
 
-                    break;
+                if let Some(time) = physics::sweep(&missile_rectangle, relative_displacement, &asteroid_rectangle) {
+                    if first_hit.is_none_or(|(first_time, ..)| time < first_time) {
+                        first_hit = Some((time, row, asteroid_i));
+                    }
                 }
             }
+
+            if let Some((_, row, asteroid_i)) = first_hit {
+                missile.missile_type.kind().on_collision(self, missile, (row, asteroid_i), asteroid_grid);
+                self.missiles[missile_i] = *missile;
+            }
+        }
+    }
+
+    /// Takes `damage` sizes off an asteroid and scores the hit.
+    pub fn damage_asteroid(&mut self, (row, asteroid_i): AsteroidKey, damage: u8) {
+        let asteroid = &mut self.asteroids[row][asteroid_i];
+        let size = asteroid.size;
+        asteroid.size = size.saturating_sub(damage);
+        let destroyed = asteroid.size == 0;
+
+        self.register_hit(size, destroyed);
+    }
+
+    /// Damages every asteroid with a part within `radius` of `center`.
+    ///
+    /// `damage` is dealt at the center and `falloff` is the share of it lost at the edge of
+    /// the radius, 0 damaging evenly and 1 fading out to nothing.
+    pub fn explode(
+        &mut self,
+        center: Position,
+        radius: f32,
+        damage: u8,
+        falloff: f32,
+        asteroid_grid: &SpatialGrid<AsteroidKey>,
+    ) {
+        let blast_rectangle = Rectangle {
+            position: center,
+            size: Size::Square((radius * 2.0).ceil() as u32),
+        };
+
+        for (row, asteroid_i) in asteroid_grid.query(&blast_rectangle) {
+            let asteroid = self.asteroids[row][asteroid_i];
+            if asteroid.size == 0 {
+                continue;
+            }
+
+            // Distance to the closest point of the asteroid
+            let corners = asteroid.rectangle().get_corners();
+            let closest = Position {
+                x: center.x.clamp(corners.top_left.x, corners.bottom_right.x),
+                y: center.y.clamp(corners.top_left.y, corners.bottom_right.y),
+            };
+            let distance = (closest - center).length();
+            if distance > radius {
+                continue;
+            }
+
+            let asteroid_damage = (damage as f32 * (1.0 - falloff * distance / radius)).ceil();
+            if asteroid_damage >= 1.0 {
+                self.damage_asteroid((row, asteroid_i), asteroid_damage as u8);
+            }
+        }
+
+        self.explosions.push(Explosion {
+            center,
+            radius,
+            tick: self.current_tick,
+        });
+    }
+
+    /// Awards the points of a missile hit, `asteroid_size` being the size before the hit.
+    ///
+    /// Every `hits_per_multiplier` hits in a row raise the multiplier by one.
+    pub fn register_hit(&mut self, asteroid_size: u8, destroyed: bool) {
+        let settings = settings::get();
+        let mut points = asteroid_size as u64 * settings.hit_points;
+        if destroyed {
+            points += settings.destroy_points;
+        }
+
+        self.score.points += points * self.score.multiplier as u64;
+
+        let weapon_level = (self.score.points / settings.points_per_weapon_level + 1).min(MAX_WEAPON_LEVEL as u64) as u8;
+        self.spaceship.weapon_level = self.spaceship.weapon_level.max(weapon_level);
+        self.score.last_hit_tick = self.current_tick;
+        self.score.combo_hits += 1;
+
+        if self.score.combo_hits >= settings.hits_per_multiplier {
+            self.score.combo_hits = 0;
+            self.score.multiplier = (self.score.multiplier + 1).min(settings.max_score_multiplier);
+        }
+    }
+
+    /// Drops the multiplier by one for every `combo_decay_delay` milliseconds without a hit.
+    fn decay_combo(&mut self) {
+        let settings = settings::get();
+        if self.current_tick < self.score.last_hit_tick + time::to_ticks(settings.combo_decay_delay) {
+            return;
+        }
+
+        self.score.combo_hits = 0;
+        if self.score.multiplier > 1 {
+            self.score.multiplier -= 1;
+            self.score.last_hit_tick = self.current_tick;
         }
     }
 
     fn next_generation(&mut self) {
+        let settings = settings::get();
         if !self.debugging || self.debug_options.asteroid_generation {
-            if let State::NextGen(next_gen_timestamp) = self.state {
-                if time::now() >= next_gen_timestamp {
+            if let State::NextGen(next_gen_tick) = self.state {
+                if self.current_tick >= next_gen_tick {
                         self.asteroids = Asteroid::initialize_asteroids(
-                            settings::ASTEROIDS_ROWS,
-                            settings::MIN_GENERATED_ASTEROIDS,
-                            settings::MAX_GENERATED_ASTEROIDS,
+                            &mut self.rng,
+                            settings.asteroids_rows(),
+                            settings.min_generated_asteroids,
+                            settings.max_generated_asteroids,
                         );
+                        self.generation += 1;
+                        self.spaceship.magazine.refill();
                         self.state = State::Running;
                 }
             }
@@ -226,6 +565,7 @@ impl Game {
     }
 
     fn check_next_generation(&mut self) {
+        let settings = settings::get();
         if let State::Running = self.state {
             let mut asteroid_count = 0;
             
@@ -233,7 +573,7 @@ impl Game {
                 asteroid_count += row.len();
             }
             if asteroid_count == 0 {
-                self.state = State::NextGen(time::now() + settings::NEXT_GENERATION_DELAY);
+                self.state = State::NextGen(self.current_tick + time::to_ticks(settings.next_generation_delay));
             }
         }
     }
@@ -241,133 +581,218 @@ impl Game {
     fn update(&mut self) {
         self.check_next_generation();
         self.next_generation();
-        self.check_spaceship_crash();
+
+        let mut asteroid_grid = Asteroid::grid(&self.asteroids);
+
+        self.update_knockback();
+        self.check_spaceship_crash(&asteroid_grid);
+        self.update_weapons();
         self.shot();
-        self.check_missile_collision();
+        self.update_laser(&asteroid_grid);
+        self.check_missile_collision(&asteroid_grid);
+        self.decay_combo();
 
-        Missile::update_missiles_position(&mut self.missiles);
+        let current_tick = self.current_tick;
+        self.explosions.retain(|explosion| current_tick < explosion.tick + time::to_ticks(EXPLOSION_DEBUG_DELAY));
+
+        self.update_missiles(&asteroid_grid);
 
-        self.asteroids_generation();
+        self.asteroids_generation(&mut asteroid_grid);
         Asteroid::update_asteroids_positions(&mut self.asteroids);
 
         Asteroid::unload_unused_asteroids(&mut self.asteroids);
+
+        self.current_tick += 1;
+    }
+
+    /// Lets the kind of every active missile act on it, then moves them all.
+    fn update_missiles(&mut self, asteroid_grid: &SpatialGrid<AsteroidKey>) {
+        for missile_i in 0..self.missiles.len() {
+            let mut missile = self.missiles[missile_i];
+            if !missile.active { continue; }
+
+            missile.missile_type.kind().on_update(self, &mut missile, asteroid_grid);
+            self.missiles[missile_i] = missile;
+        }
+
+        Missile::update_missiles_position(&mut self.missiles);
     }
 
-    fn get_inputs(&mut self, rx: &Receiver<U2GMessage>) {
-        let rx_message = rx.try_iter();
+    fn die(&mut self) {
+        self.state = State::Died;
+        self.scenes.push(Scene::GameOver);
+    }
 
-        for message in rx_message {
-            match message {
-                U2GMessage::MouseMotion(mouse_position) => {
-                    self.spaceship.position.y = mouse_position.y - settings::SPACESHIP_HEIGHT as i32 / 2;
+    fn get_inputs(&mut self, input_source: &mut dyn InputSource) {
+        for message in input_source.inputs() {
+            self.handle_input(message);
+        }
+    }
+
+    pub fn handle_input(&mut self, message: U2GMessage) {
+        let settings = settings::get();
+        match message {
+            U2GMessage::MouseMotion(mouse_position) => {
+                self.spaceship.position.y = mouse_position.y - settings.spaceship_height as f32 / 2.0;
+            }
+            U2GMessage::StartShooting(missile_type) => {
+                self.spaceship.missile_type = missile_type;
+                self.spaceship.shooting = true;
+            }
+            U2GMessage::StopShooting(missile_type) => {
+                self.spaceship.missile_type = missile_type;
+                self.spaceship.shooting = false;
+            }
+            U2GMessage::StartLaser => {
+                self.spaceship.laser.firing = true;
+            }
+            U2GMessage::StopLaser => {
+                self.spaceship.laser.firing = false;
+            }
+            U2GMessage::TogglePause => {
+                match self.scenes.current() {
+                    Scene::InGame => self.scenes.push(Scene::PauseMenu),
+                    Scene::PauseMenu => self.scenes.pop(),
+
+                    _ => {}
                 }
-                U2GMessage::Event(event) => {
-                    match event {
-
-                        Event::MouseButtonDown {
-                            mouse_btn: MouseButton::Left,
-                            ..
-                        } => {
-                            self.spaceship.missile_type = MissileType::Normal;
-                            self.spaceship.shooting = true;
-                        }
-                        Event::MouseButtonUp {
-                            mouse_btn: MouseButton::Left,
-                            ..
-                        } => {
-                            self.spaceship.missile_type = MissileType::Normal;
-                            self.spaceship.shooting = false;
-                        }
-
-                        Event::MouseButtonDown {
-                            mouse_btn: MouseButton::Right,
-                            ..
-                        } => {
-                            self.spaceship.missile_type = MissileType::Bomb;
-                            self.spaceship.shooting = true;
-                        }
-                        Event::MouseButtonUp {
-                            mouse_btn: MouseButton::Right,
-                            ..
-                        } => {
-                            self.spaceship.missile_type = MissileType::Bomb;
-                            self.spaceship.shooting = false;
-                        }
-        
-                        Event::KeyDown {
-                            keycode: Some(Keycode::Escape),
-                            ..
-                        } => {
-                            match self.state {
-                                State::Running => self.state = State::Paused,
-                                State::Paused => self.state = State::Running,
-        
-                                _ => {}
-                            }
-                        }
-        
-                        Event::KeyDown {
-                            keycode: Some(Keycode::F5),
-                            ..
-                        } => {
-                            self.debugging = !self.debugging; 
-                        }
-                        Event::KeyDown {
-                            keycode: Some(Keycode::F6),
-                            ..
-                        } => {
-                            self.debug_options = DebugOptions {
-                                generation_line: !self.debug_options.generation_line,
-                                ..self.debug_options
-                            }
-                        }
-                        Event::KeyDown {
-                            keycode: Some(Keycode::F7),
-                            ..
-                        } => {
-                            self.debug_options = DebugOptions {
-                                rows: !self.debug_options.rows,
-                                ..self.debug_options
-                            }
-                        }
-                        Event::KeyDown {
-                            keycode: Some(Keycode::F8),
-                            ..
-                        } => {
-                            self.debug_options = DebugOptions {
-                                game_state: !self.debug_options.game_state,
-                                ..self.debug_options
-                            }
-                        }
-                        Event::KeyDown {
-                            keycode: Some(Keycode::F9),
-                            ..
-                        } => {
-                            self.debug_options = DebugOptions {
-                                invincible: !self.debug_options.invincible,
-                                ..self.debug_options
-                            }
-                        }
-                        Event::KeyDown {
-                            keycode: Some(Keycode::F10),
-                            ..
-                        } => {
-                            self.debug_options = DebugOptions {
-                                asteroid_generation: !self.debug_options.asteroid_generation,
-                                ..self.debug_options
-                            }
-                        }
-        
-                        Event::Quit {..} => {
-                            self.state = State::Closed;
-                        }
-                        _ => {}
-                    }
+            }
+            U2GMessage::MenuUp => {
+                self.scenes.select_previous();
+            }
+            U2GMessage::MenuDown => {
+                self.scenes.select_next();
+            }
+            U2GMessage::MenuHover(item_index) => {
+                self.scenes.select(item_index);
+            }
+            U2GMessage::MenuClick(item_index) => {
+                if item_index < self.scenes.current().menu_items().len() {
+                    self.scenes.select(item_index);
+                    self.activate_selected_item();
+                }
+            }
+            U2GMessage::MenuSelect => {
+                self.activate_selected_item();
+            }
+            U2GMessage::Back => {
+                match self.scenes.current() {
+                    Scene::InGame => self.scenes.push(Scene::PauseMenu),
+                    Scene::TitleMenu | Scene::GameOver => {}
+
+                    _ => self.scenes.pop(),
+                }
+            }
+            U2GMessage::ToggleDebugging => {
+                self.debugging = !self.debugging;
+            }
+            U2GMessage::ToggleGenerationLine => {
+                self.debug_options = DebugOptions {
+                    generation_line: !self.debug_options.generation_line,
+                    ..self.debug_options
+                }
+            }
+            U2GMessage::ToggleRows => {
+                self.debug_options = DebugOptions {
+                    rows: !self.debug_options.rows,
+                    ..self.debug_options
+                }
+            }
+            U2GMessage::ToggleGameState => {
+                self.debug_options = DebugOptions {
+                    game_state: !self.debug_options.game_state,
+                    ..self.debug_options
+                }
+            }
+            U2GMessage::ToggleInvincible => {
+                self.debug_options = DebugOptions {
+                    invincible: !self.debug_options.invincible,
+                    ..self.debug_options
                 }
-                U2GMessage::Close => {
+            }
+            U2GMessage::ToggleAsteroidGeneration => {
+                self.debug_options = DebugOptions {
+                    asteroid_generation: !self.debug_options.asteroid_generation,
+                    ..self.debug_options
+                }
+            }
+            U2GMessage::QuickSave => {
+                self.quick_save();
+            }
+            U2GMessage::QuickLoad => {
+                self.quick_load();
+            }
+            U2GMessage::Restart => {
+                if let Scene::GameOver = self.scenes.current() {
+                    self.restart();
+                }
+            }
+            U2GMessage::Quit => {
+                if let Scene::GameOver = self.scenes.current() {
                     self.state = State::Closed;
                 }
             }
+            U2GMessage::Close => {
+                self.state = State::Closed;
+            }
+        }
+    }
+
+    fn activate_selected_item(&mut self) {
+        let item = match self.scenes.selected() {
+            Some(item) => item,
+            None => return,
+        };
+
+        match item {
+            MenuItem::Play => {
+                // The game created on launch is still untouched the first time
+                if self.current_tick > 0 {
+                    self.restart();
+                }
+                self.scenes.reset(Scene::InGame);
+            }
+            MenuItem::Options => self.scenes.push(Scene::Options),
+            MenuItem::HighScores => self.scenes.push(Scene::HighScores),
+            MenuItem::Resume | MenuItem::Back => self.scenes.pop(),
+            MenuItem::Restart => self.restart(),
+            MenuItem::MainMenu => self.scenes.reset(Scene::TitleMenu),
+            MenuItem::Debugging => self.debugging = !self.debugging,
+            MenuItem::Quit => self.state = State::Closed,
+        }
+    }
+
+    /// Replaces the game with a fresh one, keeping the high scores and debug settings.
+    ///
+    /// The new seed comes from the current RNG so restarts are reproduced by replays.
+    fn restart(&mut self) {
+        let mut game = Game::with_seed(self.rng.gen());
+        game.high_scores = std::mem::take(&mut self.high_scores);
+        game.debugging = self.debugging;
+        game.debug_options = self.debug_options.clone();
+        game.settings_error = self.settings_error.take();
+
+        *self = game;
+    }
+
+    fn quick_save(&mut self) {
+        match Snapshot::take(self).save(settings::QUICK_SAVE_FILE_PATH) {
+            Ok(()) => log::info!("Saved the game to {}", settings::QUICK_SAVE_FILE_PATH),
+            Err(error) => log::error!("{}", error),
+        }
+    }
+
+    fn quick_load(&mut self) {
+        match Snapshot::load(settings::QUICK_SAVE_FILE_PATH) {
+            Ok(snapshot) => {
+                let high_scores = self.high_scores.clone();
+                let settings_error = self.settings_error.take();
+                *self = snapshot.restore();
+                self.high_scores = high_scores;
+                self.settings_error = settings_error;
+                log::info!("Loaded the game from {}", settings::QUICK_SAVE_FILE_PATH);
+            }
+            Err(error) => log::error!("{}", error),
         }
     }
 
@@ -375,23 +800,100 @@ impl Game {
         ::std::thread::sleep(std::time::Duration::new(0, 1_000_000_000u32 / fps));
     }
 
-    pub fn init(&mut self, tx: &Sender<G2UMessage>, rx: &Receiver<U2GMessage>) {
-        'main_loop: loop {
-            match self.state {
-                State::Running | State::Paused | State::NextGen(..) => {
-                    self.get_inputs(rx);
+    fn save_high_score(&mut self) {
+        let settings = settings::get();
+        self.high_score_rank = self.high_scores.insert(HighScore {
+            score: self.score.points,
+            generation: self.generation,
+            duration: self.current_tick / settings.ticks_per_second as u64,
+            seed: self.seed,
+            date: (time::now() / 1_000_000_000) as u64,
+        });
+
+        if self.high_score_rank.is_some() {
+            if let Err(error) = self.high_scores.save() {
+                log::error!("{}", error);
+            }
+        }
+    }
 
-                    if let State::Running | State::NextGen(..) = &self.state {
-                        self.update()
-                    }
-                                    
-                    tx.send(G2UMessage::StateUpdate(self.clone())).unwrap();
-                    Game::delay_fps(settings::FPS);
-                }
-                _ => {
-                    break 'main_loop;
+    pub fn is_over(&self) -> bool {
+        !matches!(self.state, State::Running | State::NextGen(..))
+    }
+
+    /// Consumes the pending inputs and advances the simulation by one tick.
+    ///
+    /// The simulation stays frozen while a menu is open or the game is over, but the inputs
+    /// are still consumed once per tick, which is what keeps replays in step.
+    pub fn tick(&mut self, input_source: &mut dyn InputSource) {
+        self.get_inputs(input_source);
+
+        if let (State::Running | State::NextGen(..), Scene::InGame) = (&self.state, self.scenes.current()) {
+            self.update()
+        }
+    }
+
+    /// Applies the tuning values of the settings file once it's saved, the new values are used
+    /// from the next tick on. Invalid files are rejected and the current settings are kept.
+    fn reload_settings(&mut self, settings_watcher: &mut settings::Watcher) {
+        match settings_watcher.poll() {
+            Some(Ok(reloaded)) => {
+                settings::set(settings::get().with_tuning_of(&reloaded));
+                self.settings_error = None;
+                log::info!("Reloaded the settings");
+            }
+            Some(Err(error)) => {
+                log::warn!("{}", error);
+                self.settings_error = Some(error);
+            }
+            None => {}
+        }
+    }
+
+    /// Runs the simulation at a fixed timestep, independently of the frame rate.
+    ///
+    /// Every frame the elapsed wall-clock time is accumulated and consumed in whole ticks,
+    /// the leftover fraction of a tick is sent along with the state so the UI can
+    /// interpolate positions between the last two ticks. Without a `settings_watcher` the
+    /// settings file isn't reloaded.
+    pub fn init(
+        &mut self,
+        tx: &Sender<G2UMessage>,
+        input_source: &mut dyn InputSource,
+        mut settings_watcher: Option<&mut settings::Watcher>,
+    ) {
+        let settings = settings::get();
+        let tick_duration = time::tick_duration();
+        let max_accumulated_time = tick_duration * settings.max_ticks_per_frame as u128;
+
+        let mut last_frame_timestamp = time::now();
+        let mut accumulated_time: u128 = 0;
+
+        self.high_scores = HighScoreTable::load();
+
+        while !matches!(self.state, State::Closed) {
+            let now = time::now();
+            accumulated_time = (accumulated_time + now - last_frame_timestamp).min(max_accumulated_time);
+            last_frame_timestamp = now;
+
+            if let Some(settings_watcher) = settings_watcher.as_deref_mut() {
+                self.reload_settings(settings_watcher);
+            }
+
+            while accumulated_time >= tick_duration && !matches!(self.state, State::Closed) {
+                let was_over = self.is_over();
+
+                self.tick(input_source);
+                accumulated_time -= tick_duration;
+
+                if !was_over && matches!(self.state, State::Died) {
+                    self.save_high_score();
                 }
             }
+
+            let interpolation = accumulated_time as f32 / tick_duration as f32;
+            tx.send(G2UMessage::StateUpdate(self.clone(), interpolation)).unwrap();
+            Game::delay_fps(settings.fps);
         }
     }
-}
\ No newline at end of file
+}
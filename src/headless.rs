@@ -0,0 +1,80 @@
+use std::fmt;
+
+use crate::settings;
+use crate::cli::Cli;
+use crate::game::{Game, State};
+use crate::helper::InputSource;
+use crate::replay::Replay;
+
+pub struct HeadlessSettings {
+    pub ticks: u64,
+    pub script: Option<Replay>,
+}
+
+pub struct Summary {
+    pub seed: u64,
+    pub ticks: u64,
+    pub state: State,
+    pub life: u8,
+    pub score: u64,
+    pub asteroids: usize,
+    pub missiles: usize,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+"Seed: {seed}
+Ticks: {ticks}
+State: {state:?}
+Life: {life}
+Score: {score}
+Asteroids count: {asteroids}
+Missiles count: {missiles}",
+            seed=self.seed,
+            ticks=self.ticks,
+            state=self.state,
+            life=self.life,
+            score=self.score,
+            asteroids=self.asteroids,
+            missiles=self.missiles,
+        )
+    }
+}
+
+impl HeadlessSettings {
+    pub fn from_cli(cli: &Cli) -> Result<HeadlessSettings, String> {
+        let script = match &cli.script {
+            Some(path) => Some(Replay::load(path)?),
+            None => None,
+        };
+
+        return Ok(HeadlessSettings {
+            ticks: cli.ticks.unwrap_or(settings::HEADLESS_SECONDS * settings::get().ticks_per_second as u64),
+            script,
+        });
+    }
+}
+
+/// Steps the game loop `ticks` times without a window, or until it's closed.
+///
+/// Dying doesn't stop it, since a replay may restart the game afterwards.
+pub fn run(game: &mut Game, ticks: u64, input_source: &mut dyn InputSource) -> Summary {
+    for _ in 0..ticks {
+        if let State::Closed = game.state {
+            break;
+        }
+        game.tick(input_source);
+    }
+
+    return Summary {
+        seed: game.seed,
+        ticks: game.current_tick,
+        state: game.state.clone(),
+        life: game.spaceship.life,
+        score: game.score.points,
+        asteroids: game.asteroids.iter().map(|row| row.len()).sum(),
+        missiles: game.missiles.iter().filter(|missile| missile.active).count(),
+    };
+}
@@ -1,14 +1,49 @@
-use sdl2::event::Event;
+use std::sync::mpsc::Receiver;
 
 use crate::physics::Position;
 use crate::game::Game;
+use crate::missile::MissileType;
 
 pub enum G2UMessage {
-    StateUpdate(Game)
+    /// The latest state and how far (from 0 to 1) the frame is into the next tick.
+    StateUpdate(Game, f32)
 }
 
+#[derive(Clone, Copy, Debug)]
 pub enum U2GMessage {
     MouseMotion(Position),
-    Event(Event),
+    StartShooting(MissileType),
+    StopShooting(MissileType),
+    StartLaser,
+    StopLaser,
+    TogglePause,
+    MenuUp,
+    MenuDown,
+    MenuSelect,
+    /// The mouse is over the menu item with that index.
+    MenuHover(usize),
+    MenuClick(usize),
+    Back,
+    ToggleDebugging,
+    ToggleGenerationLine,
+    ToggleRows,
+    ToggleGameState,
+    ToggleInvincible,
+    ToggleAsteroidGeneration,
+    QuickSave,
+    QuickLoad,
+    Restart,
+    Quit,
     Close,
-}
\ No newline at end of file
+}
+
+/// Where the game takes its inputs from, it's called exactly once per tick.
+pub trait InputSource {
+    fn inputs(&mut self) -> Vec<U2GMessage>;
+}
+
+impl InputSource for Receiver<U2GMessage> {
+    fn inputs(&mut self) -> Vec<U2GMessage> {
+        return self.try_iter().collect();
+    }
+}
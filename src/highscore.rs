@@ -0,0 +1,106 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Serialize, Deserialize};
+
+use crate::settings;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HighScore {
+    pub score: u64,
+    pub generation: u32,
+    /// In seconds.
+    pub duration: u64,
+    pub seed: u64,
+    /// Unix timestamp, in seconds.
+    pub date: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct HighScoreTable {
+    pub entries: Vec<HighScore>,
+}
+
+impl HighScoreTable {
+    /// `highscores.ron` inside the user data directory of the platform.
+    pub fn file_path() -> Option<PathBuf> {
+        let data_dir = if cfg!(target_os = "windows") {
+            env::var_os("APPDATA").map(PathBuf::from)
+        } else if cfg!(target_os = "macos") {
+            env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+        } else {
+            env::var_os("XDG_DATA_HOME")
+                .map(PathBuf::from)
+                .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        };
+
+        return data_dir.map(|data_dir| {
+            data_dir
+                .join(settings::DATA_DIRECTORY_NAME)
+                .join(settings::HIGH_SCORES_FILE_NAME)
+        });
+    }
+
+    /// Missing or corrupt files give an empty table instead of an error, a corrupt
+    /// file is only reported and gets overwritten on the next save.
+    pub fn load() -> HighScoreTable {
+        let path = match HighScoreTable::file_path() {
+            Some(path) => path,
+            None => return HighScoreTable::default(),
+        };
+
+        let source = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(_) => return HighScoreTable::default(),
+        };
+
+        match ron::from_str::<HighScoreTable>(&source) {
+            Ok(mut table) => {
+                table.sort();
+                return table;
+            }
+            Err(error) => {
+                log::warn!("Ignoring corrupt high scores file {}: {}", path.display(), error);
+                return HighScoreTable::default();
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = HighScoreTable::file_path().ok_or("Could not find the user data directory")?;
+
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)
+                .map_err(|error| format!("Failed to create {}: {}", directory.display(), error))?;
+        }
+
+        let serialized = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|error| format!("Failed to serialize the high scores: {}", error))?;
+
+        return fs::write(&path, serialized)
+            .map_err(|error| format!("Failed to write {}: {}", path.display(), error));
+    }
+
+    fn sort(&mut self) {
+        self.entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+        self.entries.truncate(settings::HIGH_SCORES_COUNT);
+    }
+
+    /// Adds the run to the table, returning its rank if it made it into the top `HIGH_SCORES_COUNT`.
+    pub fn insert(&mut self, high_score: HighScore) -> Option<usize> {
+        let rank = self.entries
+            .iter()
+            .position(|entry| high_score.score > entry.score)
+            .unwrap_or(self.entries.len());
+
+        if rank >= settings::HIGH_SCORES_COUNT {
+            return None;
+        }
+
+        self.entries.insert(rank, high_score);
+        self.entries.truncate(settings::HIGH_SCORES_COUNT);
+
+        return Some(rank);
+    }
+}
@@ -0,0 +1,80 @@
+use serde::{Serialize, Deserialize};
+
+use crate::settings;
+use crate::physics::Position;
+use crate::rectangle::{Rectangle, Size, RectangleSize};
+
+/// The beam of the laser during the last tick, going right from `start` until `end`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Beam {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Beam {
+    pub fn rectangle(&self) -> Rectangle {
+        let length = (self.end.x - self.start.x).max(0.0);
+
+        return Rectangle {
+            position: Position {
+                x: self.start.x + length / 2.0,
+                y: self.start.y,
+            },
+            size: Size::Rectangle(RectangleSize {
+                width: length.ceil() as u32,
+                height: settings::get().laser_width,
+            }),
+        };
+    }
+}
+
+/// A hitscan weapon of the spaceship, firing drains its energy which recharges
+/// while it's released.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Laser {
+    pub firing: bool,
+    pub energy: f32,
+    /// Ran out of energy, it stays off until it recharged to `laser_recovery` or it's released.
+    pub depleted: bool,
+    /// Damage dealt but not taken off an asteroid yet, sizes only go down one at a time.
+    pub damage: f32,
+    pub beam: Option<Beam>,
+}
+
+impl Laser {
+    /// A laser with all its energy.
+    pub fn charged() -> Laser {
+        return Laser {
+            firing: false,
+            energy: settings::get().laser_energy,
+            depleted: false,
+            damage: 0.0,
+            beam: None,
+        };
+    }
+
+    /// Spends the energy of a tick of firing, false when there isn't enough left.
+    pub fn drain(&mut self) -> bool {
+        let settings = settings::get();
+        let drain = settings.laser_drain / settings.ticks_per_second as f32;
+        if !self.firing || self.depleted {
+            return false;
+        }
+        if self.energy < drain {
+            self.depleted = true;
+            return false;
+        }
+
+        self.energy -= drain;
+        return true;
+    }
+
+    pub fn recharge(&mut self) {
+        let settings = settings::get();
+        self.energy = (self.energy + settings.laser_recharge / settings.ticks_per_second as f32)
+            .min(settings.laser_energy);
+        if self.depleted && (!self.firing || self.energy >= settings.laser_recovery) {
+            self.depleted = false;
+        }
+    }
+}
@@ -8,28 +8,129 @@ pub mod game;
 pub mod ui;
 pub mod physics;
 pub mod missile;
+pub mod laser;
+pub mod ammo;
 pub mod asteroid;
+pub mod headless;
+pub mod replay;
+pub mod snapshot;
+pub mod highscore;
+pub mod scene;
+pub mod cli;
+pub mod benchmark;
 
 use  std::sync::mpsc;
 
+use cli::Cli;
 use ui::UiSettings;
 use game::Game;
+use helper::InputSource;
+use replay::{Replay, Player, Recorder};
+use scene::Scene;
+
+fn exit_with_error(error: String) -> ! {
+    eprintln!("{}", error);
+    std::process::exit(1);
+}
+
+fn save_recording<S: InputSource>(recorder: &Recorder<S>, path: Option<&String>) {
+    if let Some(path) = path {
+        if let Err(error) = recorder.replay.save(path) {
+            exit_with_error(error);
+        }
+    }
+}
+
+/// Closing the channel ends the UI loop, the input receiver must outlive it.
+fn close_ui(tx: mpsc::Sender<helper::G2UMessage>, ui_thread: std::thread::JoinHandle<()>) {
+    drop(tx);
+    ui_thread.join().unwrap();
+}
 
 fn main() {
+    let cli = Cli::parse_args();
+
+    env_logger::Builder::new()
+        .filter_level(cli.log_level)
+        .init();
+
+    let loaded_settings = cli.settings().unwrap_or_else(|error| exit_with_error(error));
+    settings::set(loaded_settings);
+
+    if cli.benchmark {
+        benchmark::run();
+        return;
+    }
+
+    let replay = cli.replay
+        .as_ref()
+        .map(|path| Replay::load(path).unwrap_or_else(|error| exit_with_error(error)));
+    if let (Some(path), Some(replay)) = (&cli.replay, &replay) {
+        if !replay.matches_settings() {
+            log::warn!("{} was recorded with other settings, it may not play out the same", path);
+        }
+    }
+    let record_path = cli.record.as_ref();
+
+    let seed = cli.seed.or(replay.as_ref().and_then(|replay| replay.seed));
+
+    let mut game = match seed {
+        Some(seed) => Game::with_seed(seed),
+        None => Game::new(),
+    };
+
+    log::info!("Starting with seed {}", game.seed);
+
+    if cli.headless {
+        let headless_settings = headless::HeadlessSettings::from_cli(&cli)
+            .unwrap_or_else(|error| exit_with_error(error));
+
+        let script = replay.or(headless_settings.script).unwrap_or(Replay::new(None, false));
+        if script.title_menu {
+            game.scenes.reset(Scene::TitleMenu);
+        }
+        let mut recorder = Recorder::new(Player::new(script.clone(), None), game.seed, script.title_menu);
+
+        println!("{}", headless::run(&mut game, headless_settings.ticks, &mut recorder));
+        save_recording(&recorder, record_path);
+        return;
+    }
+
+    let mut settings_watcher = settings::Watcher::new(cli.config_path());
+
     let (g2u_tx, g2u_rx) = mpsc::channel::<helper::G2UMessage>();
     let (u2g_tx, u2g_rx) = mpsc::channel::<helper::U2GMessage>();
 
-    std::thread::spawn(move || {
+    let ui_thread = std::thread::spawn(move || {
+        let settings = settings::get();
         let mut window = ui::Ui::new(UiSettings {
-            title: String::from(settings::WINDOW_TITLE),
-            width: settings::WINDOW_WIDTH,
-            height: settings::WINDOW_HEIGHT,
+            title: String::from(&settings.window_title),
+            width: settings.window_width,
+            height: settings.window_height,
+            fullscreen: settings.fullscreen,
         });
 
         window.run(&u2g_tx, &g2u_rx);
     });
 
-    let mut game = Game::new();
-
-    game.init(&g2u_tx, &u2g_rx);
-}
\ No newline at end of file
+    if let Some(replay) = replay {
+        if replay.title_menu {
+            game.scenes.reset(Scene::TitleMenu);
+        }
+        let mut player = Player::new(replay, Some(u2g_rx));
+        // Edits to the settings would make the playback drift from the recorded run
+        game.init(&g2u_tx, &mut player, None);
+        close_ui(g2u_tx, ui_thread);
+    } else if record_path.is_some() {
+        game.scenes.reset(Scene::TitleMenu);
+        let mut recorder = Recorder::new(u2g_rx, game.seed, true);
+        game.init(&g2u_tx, &mut recorder, Some(&mut settings_watcher));
+        close_ui(g2u_tx, ui_thread);
+        save_recording(&recorder, record_path);
+    } else {
+        game.scenes.reset(Scene::TitleMenu);
+        let mut input = u2g_rx;
+        game.init(&g2u_tx, &mut input, Some(&mut settings_watcher));
+        close_ui(g2u_tx, ui_thread);
+    }
+}
@@ -1,74 +1,62 @@
-use crate::physics::{ChangingFactor};
-use crate::missile::{MissileData, Missile};
+use sdl2::pixels::Color;
+
+use crate::physics::{Body, Vector, SpatialGrid};
+use crate::missile::{MissileData, MissileKind, RenderHint, Missile};
 use crate::game::Game;
 use crate::time;
-use crate::asteroid::Asteroid;
+use crate::settings;
+use crate::asteroid::AsteroidKey;
+use crate::ammo::Ammo;
 use crate::missile;
 use missile::MissileType;
-use missile::missiles::Normal;
 
 
 
 pub struct Bomb {}
 
 impl Bomb {
-    pub fn get_missile_data() -> MissileData {
+    pub const ID: MissileType = MissileType::new("bomb");
+
+    /// Share of the damage lost at the edge of the blast.
+    const FALLOFF: f32 = 0.5;
+}
+
+impl MissileKind for Bomb {
+    fn id(&self) -> MissileType {
+        return Bomb::ID;
+    }
+
+    fn display_name(&self) -> &'static str {
+        return "Bombs";
+    }
+
+    fn data(&self, _level: u8) -> MissileData {
         return MissileData {
-            initial_velocity: ChangingFactor {
-                x: 3.0,
-                y: 0.0,
-            },
-            direction: ChangingFactor {
-                x: 1.0,
-                y: 0.0,
-            },
-            acceleration: ChangingFactor {
-                x: 20.0,
-                y: 0.0,
-            },
-            delay: time::to_nano(1000 / 10),
+            // Heavy and slow to launch, its engine speeds it up on the way
+            body: Body::new(Vector::new(3.0, 0.0), 4.0, Vector::new(0.2, 0.0)),
+            delay: time::to_ticks(settings::get().bomb_missile_delay),
+            damage: settings::get().bomb_damage,
         }
     }
 
-    pub fn collision_handler(game: &mut Game, missile: &mut Missile, asteroid: &mut Asteroid) {
-        (*missile).active = false;
-        (*asteroid).size -= 1;
+    /// Blows up where it hit, the asteroids around the hit one take less damage.
+    fn on_collision(&self, game: &mut Game, missile: &mut Missile, _asteroid: AsteroidKey, asteroid_grid: &SpatialGrid<AsteroidKey>) {
+        let settings = settings::get();
+        missile.active = false;
 
-        let lookup_directions_table = [
-            (-1.0, -1.0),
-            (0.0, -1.0),
-            (1.0, -1.0),
-            (1.0, 0.0),
-            (1.0, 1.0),
-            (0.0, 1.0),
-            (-1.0, 1.0),
-        ];
+        game.explode(missile.position, settings.bomb_blast_radius, settings.bomb_damage, Bomb::FALLOFF, asteroid_grid);
+    }
 
-        for missile_i in 0..6 {
-            let next_missile = Missile {
-                active: true,
-                velocity: ChangingFactor {
-                    x: Normal::get_missile_data().initial_velocity.x / 2.0,
-                    y: Normal::get_missile_data().initial_velocity.x / 2.0,
-                },
-                acceleration: ChangingFactor {
-                    x: 0.0,
-                    y: 0.0,
-                },
-                direction: ChangingFactor {
-                    x: lookup_directions_table[missile_i].0,
-                    y: lookup_directions_table[missile_i].1,
-                },
-                collision_handler: Normal::collision_handler,
-                missile_type: MissileType::Normal,
-                position: missile.position,
-            };
-            
-            if missile_i == 0 {
-                *missile = next_missile;
-            } else {
-                game.missiles.push(next_missile);
-            }
-        }
+    fn ammo(&self) -> Option<Ammo> {
+        return Some(Ammo {
+            capacity: settings::get().bomb_charges,
+            reload_delay: None,
+        });
+    }
+
+    fn render_hint(&self) -> RenderHint {
+        return RenderHint {
+            tint: Color::RGB(255, 170, 90),
+        };
     }
-}
\ No newline at end of file
+}
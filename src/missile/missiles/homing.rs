@@ -0,0 +1,98 @@
+use sdl2::pixels::Color;
+
+use crate::physics::{Body, Vector, SpatialGrid};
+use crate::missile::{MissileData, MissileKind, MissileType, RenderHint, Missile};
+use crate::game::Game;
+use crate::asteroid::AsteroidKey;
+use crate::ammo::Ammo;
+use crate::time;
+use crate::settings;
+
+/// Slow to reload, but turns towards the nearest asteroid on every tick until it
+/// hits something or burns out.
+pub struct Homing {}
+
+impl Homing {
+    pub const ID: MissileType = MissileType::new("homing");
+
+    /// Position of the closest asteroid still in the game.
+    fn nearest_target(game: &Game, missile: &Missile) -> Option<Vector> {
+        let mut nearest: Option<(f32, Vector)> = None;
+
+        for row in game.asteroids.iter() {
+            for asteroid in row.iter() {
+                if asteroid.size == 0 {
+                    continue;
+                }
+
+                let distance = (asteroid.position - missile.position).length();
+                if nearest.is_none_or(|(nearest_distance, _)| distance < nearest_distance) {
+                    nearest = Some((distance, asteroid.position));
+                }
+            }
+        }
+
+        return nearest.map(|(_, position)| position);
+    }
+}
+
+impl MissileKind for Homing {
+    fn id(&self) -> MissileType {
+        return Homing::ID;
+    }
+
+    fn display_name(&self) -> &'static str {
+        return "Homing";
+    }
+
+    fn data(&self, _level: u8) -> MissileData {
+        return MissileData {
+            body: Body::new(Vector::new(7.0, 0.0), 1.0, Vector::ZERO),
+            delay: time::to_ticks(settings::get().homing_missile_delay),
+            damage: 1,
+        }
+    }
+
+    fn on_update(&self, game: &mut Game, missile: &mut Missile, _asteroid_grid: &SpatialGrid<AsteroidKey>) {
+        let settings = settings::get();
+        if game.current_tick >= missile.spawn_tick + time::to_ticks(settings.homing_missile_lifetime) {
+            missile.active = false;
+            return;
+        }
+
+        let Some(target) = Homing::nearest_target(game, missile) else {
+            return;
+        };
+
+        // Turns the velocity towards the target, keeping its speed
+        let velocity = missile.body.velocity;
+        let mut turn = (target - missile.position).angle() - velocity.angle();
+        if turn > std::f32::consts::PI {
+            turn -= std::f32::consts::TAU;
+        } else if turn < -std::f32::consts::PI {
+            turn += std::f32::consts::TAU;
+        }
+        let max_turn = settings.homing_turn_rate.to_radians();
+
+        missile.body.velocity = velocity.rotated(turn.clamp(-max_turn, max_turn));
+    }
+
+    fn on_collision(&self, game: &mut Game, missile: &mut Missile, asteroid: AsteroidKey, _asteroid_grid: &SpatialGrid<AsteroidKey>) {
+        missile.active = false;
+        missile.hit(game, asteroid);
+    }
+
+    fn ammo(&self) -> Option<Ammo> {
+        let settings = settings::get();
+        return Some(Ammo {
+            capacity: settings.homing_ammo,
+            reload_delay: Some(settings.homing_reload_delay),
+        });
+    }
+
+    fn render_hint(&self) -> RenderHint {
+        return RenderHint {
+            tint: Color::RGB(140, 255, 160),
+        };
+    }
+}
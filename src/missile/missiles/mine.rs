@@ -0,0 +1,114 @@
+use sdl2::pixels::Color;
+
+use crate::physics::{Body, Vector, SpatialGrid};
+use crate::missile::{MissileData, MissileKind, MissileType, RenderHint, Missile};
+use crate::game::Game;
+use crate::asteroid::AsteroidKey;
+use crate::ammo::Ammo;
+use crate::ui::Ui;
+use crate::rectangle::{Rectangle, Size};
+use crate::time;
+use crate::settings;
+
+/// Dropped behind the spaceship, it slowly drifts forward and once armed blows up as soon
+/// as an asteroid comes close, damaging every asteroid around.
+pub struct Mine {}
+
+impl Mine {
+    pub const ID: MissileType = MissileType::new("mine");
+
+    fn is_armed(game: &Game, mine: &Missile) -> bool {
+        return game.current_tick >= mine.spawn_tick + time::to_ticks(settings::get().mine_arming_delay);
+    }
+
+    /// Damages every asteroid within `mine_blast_radius`, less the further they are.
+    fn detonate(game: &mut Game, mine: &mut Missile, asteroid_grid: &SpatialGrid<AsteroidKey>) {
+        let settings = settings::get();
+        mine.active = false;
+
+        game.explode(mine.position, settings.mine_blast_radius, settings.mine_damage, 1.0, asteroid_grid);
+    }
+}
+
+impl MissileKind for Mine {
+    fn id(&self) -> MissileType {
+        return Mine::ID;
+    }
+
+    fn display_name(&self) -> &'static str {
+        return "Mines";
+    }
+
+    fn data(&self, _level: u8) -> MissileData {
+        return MissileData {
+            body: Body::new(Vector::new(0.5, 0.0), 1.0, Vector::ZERO),
+            delay: time::to_ticks(settings::get().mine_delay),
+            damage: settings::get().mine_damage,
+        }
+    }
+
+    /// Moves the mine to the back of the spaceship, the oldest mine is removed when too many are out.
+    fn on_spawn(&self, game: &mut Game, mine: &mut Missile) {
+        let settings = settings::get();
+        mine.position.x -= settings.spaceship_width as f32;
+        mine.previous_position = mine.position;
+
+        let active_mines = game.missiles
+            .iter()
+            .filter(|missile| missile.active && missile.missile_type == Mine::ID)
+            .count();
+        if active_mines < settings.max_active_mines as usize {
+            return;
+        }
+
+        if let Some(oldest_mine) = game.missiles
+            .iter_mut()
+            .filter(|missile| missile.active && missile.missile_type == Mine::ID)
+            .min_by_key(|missile| missile.spawn_tick) {
+            oldest_mine.active = false;
+        }
+    }
+
+    fn on_update(&self, game: &mut Game, mine: &mut Missile, asteroid_grid: &SpatialGrid<AsteroidKey>) {
+        let settings = settings::get();
+        if game.current_tick >= mine.spawn_tick + time::to_ticks(settings.mine_lifetime) {
+            mine.active = false;
+            return;
+        }
+        if !Mine::is_armed(game, mine) {
+            return;
+        }
+
+        let trigger_rectangle = Rectangle {
+            position: mine.position,
+            size: Size::Square((settings.mine_trigger_radius * 2.0).ceil() as u32),
+        };
+        let triggered = asteroid_grid.query(&trigger_rectangle).into_iter().any(|(row, asteroid_i)| {
+            let asteroid = game.asteroids[row][asteroid_i];
+            let asteroid_radius = Ui::to_pixels(asteroid.size as u32) as f32 / 2.0;
+            return asteroid.size > 0
+                && (asteroid.position - mine.position).length() - asteroid_radius <= settings.mine_trigger_radius;
+        });
+        if triggered {
+            Mine::detonate(game, mine, asteroid_grid);
+        }
+    }
+
+    /// Asteroids go through mines, which only go off from `on_update` once an asteroid is
+    /// within `mine_trigger_radius`.
+    fn on_collision(&self, _game: &mut Game, _mine: &mut Missile, _asteroid: AsteroidKey, _asteroid_grid: &SpatialGrid<AsteroidKey>) {}
+
+    fn ammo(&self) -> Option<Ammo> {
+        let settings = settings::get();
+        return Some(Ammo {
+            capacity: settings.mine_ammo,
+            reload_delay: Some(settings.mine_reload_delay),
+        });
+    }
+
+    fn render_hint(&self) -> RenderHint {
+        return RenderHint {
+            tint: Color::RGB(255, 90, 200),
+        };
+    }
+}
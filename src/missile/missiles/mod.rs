@@ -1,5 +1,25 @@
 pub mod normal;
 pub mod bomb;
+pub mod homing;
+pub mod piercing;
+pub mod spread;
+pub mod mine;
 
 pub use normal::Normal;
-pub use bomb::Bomb;
\ No newline at end of file
+pub use bomb::Bomb;
+pub use homing::Homing;
+pub use piercing::Piercing;
+pub use spread::Spread;
+pub use mine::Mine;
+
+use crate::missile::MissileKind;
+
+/// Every missile kind of the game, a new weapon only needs its file and an entry here.
+pub static KINDS: &[&dyn MissileKind] = &[
+    &Normal {},
+    &Bomb {},
+    &Homing {},
+    &Piercing {},
+    &Spread {},
+    &Mine {},
+];
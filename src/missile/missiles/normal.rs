@@ -1,33 +1,40 @@
-use crate::physics::{ChangingFactor};
+use crate::physics::{Body, Vector, SpatialGrid};
 
 use crate::time;
-use crate::missile::{MissileData, Missile};
+use crate::settings;
+use crate::missile::{MissileData, MissileKind, MissileType, Missile};
 use crate::game::Game;
-use crate::asteroid::Asteroid;
+use crate::asteroid::AsteroidKey;
 
 pub struct Normal {}
 
 impl Normal {
-    pub fn get_missile_data() -> MissileData {
+    pub const ID: MissileType = MissileType::new("normal");
+}
+
+impl MissileKind for Normal {
+    fn id(&self) -> MissileType {
+        return Normal::ID;
+    }
+
+    fn display_name(&self) -> &'static str {
+        return "Normal";
+    }
+
+    fn data(&self, _level: u8) -> MissileData {
         return MissileData {
-            initial_velocity: ChangingFactor {
-                x: 10.0,
-                y: 0.0,
-            },
-            direction: ChangingFactor {
-                x: 1.0,
-                y: 0.0,
-            },
-            acceleration: ChangingFactor {
-                x: 0.0,
-                y: 0.0,
-            },
-            delay: time::to_nano(1000 / 15),
+            body: Body::new(Vector::new(10.0, 0.0), 1.0, Vector::ZERO),
+            delay: time::to_ticks(settings::get().normal_missile_delay),
+            damage: 1,
         }
     }
 
-    pub fn collision_handler(_game: &mut Game, missile: &mut Missile, asteroid: &mut Asteroid) {
-        (*asteroid).size -= 1;
-        (*missile).active = false;
+    fn on_collision(&self, game: &mut Game, missile: &mut Missile, asteroid: AsteroidKey, _asteroid_grid: &SpatialGrid<AsteroidKey>) {
+        missile.active = false;
+        missile.hit(game, asteroid);
+    }
+
+    fn heat(&self) -> f32 {
+        return settings::get().normal_heat_per_shot;
     }
 }
\ No newline at end of file
@@ -0,0 +1,74 @@
+use sdl2::pixels::Color;
+
+use crate::physics::{Body, Vector, SpatialGrid};
+use crate::missile::{self, MissileData, MissileKind, MissileType, RenderHint, Missile};
+use crate::game::Game;
+use crate::asteroid::AsteroidKey;
+use crate::ui::Ui;
+use crate::settings;
+
+/// A fast round going through several asteroids before it's spent.
+pub struct Piercing {}
+
+impl Piercing {
+    pub const ID: MissileType = MissileType::new("piercing");
+
+    /// Asteroids it goes through and damage dealt to each of them, by weapon level.
+    const LEVELS: [(u8, u8); 3] = [
+        (2, 1),
+        (3, 1),
+        (4, 2),
+    ];
+
+    fn level(level: u8) -> (u8, u8) {
+        return Piercing::LEVELS[(level.clamp(1, missile::MAX_WEAPON_LEVEL) - 1) as usize];
+    }
+}
+
+impl MissileKind for Piercing {
+    fn id(&self) -> MissileType {
+        return Piercing::ID;
+    }
+
+    fn display_name(&self) -> &'static str {
+        return "Piercing";
+    }
+
+    fn data(&self, level: u8) -> MissileData {
+        let (_, damage) = Piercing::level(level);
+
+        return MissileData {
+            body: Body::new(Vector::new(12.0, 0.0), 1.0, Vector::ZERO),
+            delay: missile::level_delay(settings::get().piercing_missile_delay, level),
+            damage,
+        }
+    }
+
+    fn on_spawn(&self, game: &mut Game, missile: &mut Missile) {
+        let (pierce, _) = Piercing::level(game.spaceship.weapon_level);
+        missile.pierce = pierce;
+    }
+
+    fn on_collision(&self, game: &mut Game, missile: &mut Missile, (row, asteroid_i): AsteroidKey, _asteroid_grid: &SpatialGrid<AsteroidKey>) {
+        let asteroid = game.asteroids[row][asteroid_i];
+        let asteroid_width = Ui::to_pixels(asteroid.size as u32);
+        missile.hit(game, (row, asteroid_i));
+
+        if missile.pierce == 0 {
+            missile.active = false;
+            return;
+        }
+        missile.pierce -= 1;
+
+        // Ignores collisions until it's out of the asteroid, instead of hitting it on every tick
+        let crossing = (asteroid_width + settings::get().missile_width) as f32;
+        let relative_speed = (missile.body.velocity - asteroid.body.velocity).length().max(1.0);
+        missile.passing_until_tick = game.current_tick + (crossing / relative_speed).ceil() as u64;
+    }
+
+    fn render_hint(&self) -> RenderHint {
+        return RenderHint {
+            tint: Color::RGB(120, 200, 255),
+        };
+    }
+}
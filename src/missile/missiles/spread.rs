@@ -0,0 +1,73 @@
+use sdl2::pixels::Color;
+
+use crate::physics::{Body, Vector, SpatialGrid};
+use crate::missile::{self, MissileData, MissileKind, MissileType, RenderHint, Missile};
+use crate::game::Game;
+use crate::asteroid::AsteroidKey;
+use crate::settings;
+
+/// Fires a fan of missiles, `spread_shot_fans` of them depending on the weapon level.
+pub struct Spread {}
+
+impl Spread {
+    pub const ID: MissileType = MissileType::new("spread");
+
+    /// Directions of the fan from the middle outwards, a fan of n missiles uses the first n.
+    pub const DIRECTIONS: [(f32, f32); 7] = [
+        (1.0, 0.0),
+        (1.0, -0.15),
+        (1.0, 0.15),
+        (1.0, -0.3),
+        (1.0, 0.3),
+        (1.0, -0.45),
+        (1.0, 0.45),
+    ];
+
+    /// Damage dealt by every missile of the fan, by weapon level.
+    const DAMAGES: [u8; 3] = [1, 1, 2];
+
+    const SPEED: f32 = 9.0;
+}
+
+impl MissileKind for Spread {
+    fn id(&self) -> MissileType {
+        return Spread::ID;
+    }
+
+    fn display_name(&self) -> &'static str {
+        return "Spread";
+    }
+
+    fn data(&self, level: u8) -> MissileData {
+        let level_i = (level.clamp(1, missile::MAX_WEAPON_LEVEL) - 1) as usize;
+
+        return MissileData {
+            body: Body::new(Vector::new(Spread::SPEED, 0.0), 1.0, Vector::ZERO),
+            delay: missile::level_delay(settings::get().spread_missile_delay, level),
+            damage: Spread::DAMAGES[level_i],
+        }
+    }
+
+    fn on_spawn(&self, game: &mut Game, missile: &mut Missile) {
+        let level_i = (game.spaceship.weapon_level.clamp(1, missile::MAX_WEAPON_LEVEL) - 1) as usize;
+        let fan = settings::get().spread_shot_fans[level_i] as usize;
+
+        // `missile` goes straight, the rest of the fan is added around it
+        for &(direction_x, direction_y) in Spread::DIRECTIONS.iter().take(fan).skip(1) {
+            let mut fan_missile = *missile;
+            fan_missile.body.velocity = Vector::new(direction_x, direction_y).normalized() * Spread::SPEED;
+            game.missiles.push(fan_missile);
+        }
+    }
+
+    fn on_collision(&self, game: &mut Game, missile: &mut Missile, asteroid: AsteroidKey, _asteroid_grid: &SpatialGrid<AsteroidKey>) {
+        missile.active = false;
+        missile.hit(game, asteroid);
+    }
+
+    fn render_hint(&self) -> RenderHint {
+        return RenderHint {
+            tint: Color::RGB(255, 230, 120),
+        };
+    }
+}
@@ -1,41 +1,172 @@
+use std::fmt;
+
+use sdl2::pixels::Color;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::Error;
+
 use crate::settings;
-use crate::physics;
+use crate::time;
 use crate::rectangle::{Rectangle, Size, RectangleSize};
-use physics::{Position, ChangingFactor};
+use crate::physics::{Position, Body, SpatialGrid};
 use crate::game::Game;
-use crate::asteroid::Asteroid;
+use crate::asteroid::AsteroidKey;
+use crate::ammo::Ammo;
 
 // Missile Types
 pub mod missiles;
 
 pub struct MissileData {
-    pub initial_velocity: ChangingFactor,
-    pub direction: ChangingFactor,
-    pub acceleration: ChangingFactor,
-    pub delay: u128,
+    /// Body of a missile when it's shot.
+    pub body: Body,
+    pub delay: u64,
+    /// Sizes taken off the asteroids it hits.
+    pub damage: u8,
+}
+
+/// Highest weapon level of the spaceship, levels start at 1.
+pub const MAX_WEAPON_LEVEL: u8 = 3;
+
+/// Ticks between two shots of a weapon reloading every `delay` milliseconds at level 1,
+/// every level shortening it.
+pub fn level_delay(delay: u16, level: u8) -> u64 {
+    return time::to_ticks(delay) * 3 / (level as u64 + 2);
+}
+
+/// How the Ui draws the missiles of a kind.
+pub struct RenderHint {
+    /// Color modulation of the missile sprite, white keeps it as it is.
+    pub tint: Color,
+}
+
+/// A weapon of the spaceship, every kind is registered in `missiles::KINDS`.
+///
+/// Only `on_collision` is mandatory, the other hooks do nothing by default.
+pub trait MissileKind: Sync {
+    fn id(&self) -> MissileType;
+
+    /// Name of the kind shown to the player.
+    fn display_name(&self) -> &'static str;
+
+    /// Missiles shot at weapon `level`, from 1 to `MAX_WEAPON_LEVEL`.
+    fn data(&self, level: u8) -> MissileData;
+
+    /// Called when the spaceship shoots `missile`, before it's added to the game.
+    fn on_spawn(&self, _game: &mut Game, _missile: &mut Missile) {}
+
+    /// Called every tick on the active missiles, before they move.
+    ///
+    /// `asteroid_grid` is the broadphase of the current tick, to look for asteroids nearby.
+    fn on_update(&self, _game: &mut Game, _missile: &mut Missile, _asteroid_grid: &SpatialGrid<AsteroidKey>) {}
+
+    /// Called on the first asteroid `missile` touched during the tick.
+    fn on_collision(&self, game: &mut Game, missile: &mut Missile, asteroid: AsteroidKey, asteroid_grid: &SpatialGrid<AsteroidKey>);
+
+    /// Rounds of the kind, `None` for unlimited ones.
+    fn ammo(&self) -> Option<Ammo> {
+        return None;
+    }
+
+    /// Heat a shot adds to the gun, kinds adding some can't be fired while it's overheated.
+    fn heat(&self) -> f32 {
+        return 0.0;
+    }
+
+    fn render_hint(&self) -> RenderHint {
+        return RenderHint {
+            tint: Color::WHITE,
+        };
+    }
+}
+
+/// Identifies a missile kind by its name, which is also how it's written in the
+/// replays and the snapshots.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MissileType(&'static str);
+
+impl MissileType {
+    pub const fn new(name: &'static str) -> MissileType {
+        return MissileType(name);
+    }
+
+    pub fn name(self) -> &'static str {
+        return self.0;
+    }
+
+    /// The registered kind called `name`.
+    pub fn from_name(name: &str) -> Option<MissileType> {
+        return missiles::KINDS
+            .iter()
+            .map(|kind| kind.id())
+            .find(|id| id.name() == name);
+    }
+
+    pub fn kind(self) -> &'static dyn MissileKind {
+        return *missiles::KINDS
+            .iter()
+            .find(|kind| kind.id() == self)
+            .expect("Every missile type comes from a registered kind");
+    }
 }
 
-pub type CollisionHandler = fn (&mut Game, &mut Missile, &mut Asteroid); 
+impl fmt::Debug for MissileType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
 
-#[derive(Clone, Copy)]
-pub enum MissileType {
-    Normal,
-    Bomb,
+impl Serialize for MissileType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.serialize_str(self.0);
+    }
+}
+
+impl<'de> Deserialize<'de> for MissileType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<MissileType, D::Error> {
+        let name = String::deserialize(deserializer)?;
+
+        return MissileType::from_name(&name)
+            .ok_or_else(|| D::Error::custom(format!("Unknown missile type: {}", name)));
+    }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub struct Missile {
     pub position: Position,
-    pub velocity: ChangingFactor,
-    pub direction: ChangingFactor,
-    pub acceleration: ChangingFactor,
+    pub previous_position: Position,
+    pub body: Body,
     pub active: bool,
     pub missile_type: MissileType,
-    pub collision_handler: CollisionHandler,
+    /// Tick the missile was shot (or split) on.
+    pub spawn_tick: u64,
+    pub damage: u8,
+    /// Asteroids it can still go through.
+    pub pierce: u8,
+    /// Collisions are ignored until this tick, while it goes through an asteroid.
+    pub passing_until_tick: u64,
 }
 
 impl Missile {
+    pub fn new(missile_type: MissileType, data: &MissileData, position: Position, spawn_tick: u64) -> Missile {
+        return Missile {
+            position,
+            previous_position: position,
+            body: data.body,
+            active: true,
+            missile_type,
+            spawn_tick,
+            damage: data.damage,
+            pierce: 0,
+            passing_until_tick: 0,
+        };
+    }
+
+    /// Takes the damage of the missile off `asteroid` and scores the hit.
+    pub fn hit(&self, game: &mut Game, asteroid: AsteroidKey) {
+        game.damage_asteroid(asteroid, self.damage);
+    }
+
     pub fn update_missiles_position(missiles: &mut Vec<Missile>) {
+        let settings = settings::get();
         let mut updated_missiles = vec![];
         
         for missile in missiles.iter_mut() {
@@ -49,53 +180,25 @@ impl Missile {
                     y: missile.position.y,
                 },
                 size: Size::Rectangle(RectangleSize {
-                    width: settings::MISSILE_WIDTH,
-                    height: settings::MISSILE_HEIGHT,
+                    width: settings.missile_width,
+                    height: settings.missile_height,
                 }),
             }.get_corners();
 
-            if corners.top_left.x > settings::WINDOW_WIDTH as i32
-            || corners.top_left.x < 0
-            || corners.top_left.y > settings::WINDOW_HEIGHT as i32
-            || corners.top_left.y < 0{
+            if corners.top_left.x > settings.window_width as f32
+            || corners.top_left.x < 0.0
+            || corners.top_left.y > settings.window_height as f32
+            || corners.top_left.y < 0.0 {
                 continue;
             }
 
-            
-            missile.position = physics::next_position(
-                Rectangle {
-                    position: missile.position,
-                    size: Size::Rectangle(RectangleSize {
-                        width: settings::MISSILE_WIDTH,
-                        height: settings::MISSILE_HEIGHT,
-                    }),
-                },
-                missile.velocity,
-                vec![
-                    physics::Force {
-                        direction: missile.direction,
-                        acceleration: missile.acceleration,
-                    }
-                ]
-            );
+
+            missile.previous_position = missile.position;
+            missile.position = missile.body.integrate(missile.position);
 
             updated_missiles.push(*missile);
         }
 
         *missiles = updated_missiles;
     }
-
-    pub fn get_types_data(missile_type: MissileType) -> MissileData {
-        match missile_type {
-            MissileType::Normal => missiles::Normal::get_missile_data(),
-            MissileType::Bomb => missiles::Bomb::get_missile_data(),
-        }
-    }
-
-    pub fn get_types_handler(missile_type: MissileType) -> CollisionHandler {
-        match missile_type {
-            MissileType::Normal => missiles::Normal::collision_handler,
-            MissileType::Bomb => missiles::Bomb::collision_handler,
-        }
-    }
-}
\ No newline at end of file
+}
@@ -0,0 +1,123 @@
+use serde::{Serialize, Deserialize};
+
+use crate::physics::{Position, Vector};
+
+/// Motion of a simulated entity, its position is kept by the entity itself.
+///
+/// Distances are in pixels and time in ticks, so velocities are in pixels per tick and
+/// forces in mass times pixels per tick squared.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Body {
+    pub velocity: Vector,
+    pub mass: f32,
+    /// Force applied on every tick, like an engine.
+    pub thrust: Vector,
+    /// Forces applied during the current tick, cleared once integrated.
+    pub force: Vector,
+}
+
+impl Body {
+    pub fn new(velocity: Vector, mass: f32, thrust: Vector) -> Body {
+        return Body {
+            velocity,
+            mass,
+            thrust,
+            force: Vector::ZERO,
+        };
+    }
+
+    /// Adds a force to the ones integrated on the next tick.
+    pub fn apply_force(&mut self, force: Vector) {
+        self.force += force;
+    }
+
+    pub fn acceleration(&self) -> Vector {
+        return (self.thrust + self.force) * (1.0 / self.mass);
+    }
+
+    /// Advances the body by one tick with semi-implicit Euler: the velocity is updated
+    /// first and the new velocity moves `position`, which is returned.
+    pub fn integrate(&mut self, position: Position) -> Position {
+        self.velocity += self.acceleration();
+        self.force = Vector::ZERO;
+
+        return position + self.velocity;
+    }
+
+    /// Where `integrate` would move `position`, without changing the body.
+    pub fn next_position(&self, position: Position) -> Position {
+        let mut body = *self;
+        return body.integrate(position);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Vector, expected: Vector) {
+        assert!((actual - expected).length() < 1e-3, "{:?} isn't {:?}", actual, expected);
+    }
+
+    #[test]
+    fn constant_velocity() {
+        let x0 = Position::new(10.0, -4.0);
+        let v = Vector::new(3.0, 0.5);
+        let mut body = Body::new(v, 2.0, Vector::ZERO);
+
+        let mut position = x0;
+        for n in 1..=100 {
+            position = body.integrate(position);
+            assert_close(position, x0 + v * n as f32);
+            assert_close(body.velocity, v);
+        }
+    }
+
+    #[test]
+    fn constant_force() {
+        let x0 = Position::new(5.0, 20.0);
+        let v0 = Vector::new(-2.0, 1.0);
+        let thrust = Vector::new(0.5, -0.25);
+        let mass = 2.0;
+        let a = thrust * (1.0 / mass);
+        let mut body = Body::new(v0, mass, thrust);
+
+        let mut position = x0;
+        for n in 1..=100 {
+            let n_f32 = n as f32;
+            position = body.integrate(position);
+            assert_close(body.velocity, v0 + a * n_f32);
+            assert_close(position, x0 + v0 * n_f32 + a * (n_f32 * (n_f32 + 1.0) / 2.0));
+        }
+    }
+
+    #[test]
+    fn forces_add_up_and_are_cleared() {
+        let mut body = Body::new(Vector::ZERO, 1.0, Vector::ZERO);
+        body.apply_force(Vector::new(1.0, 0.0));
+        body.apply_force(Vector::new(0.0, 2.0));
+        body.apply_force(Vector::new(-3.0, 1.0));
+        assert_close(body.acceleration(), Vector::new(-2.0, 3.0));
+
+        let position = body.integrate(Position::ZERO);
+        assert_close(body.velocity, Vector::new(-2.0, 3.0));
+        assert_close(position, Vector::new(-2.0, 3.0));
+        assert_eq!(body.force, Vector::ZERO);
+
+        // Nothing left pushing it, it keeps its velocity
+        let position = body.integrate(position);
+        assert_close(body.velocity, Vector::new(-2.0, 3.0));
+        assert_close(position, Vector::new(-4.0, 6.0));
+    }
+
+    #[test]
+    fn acceleration_scales_with_inverse_mass() {
+        let force = Vector::new(6.0, -3.0);
+        for mass in [0.5, 1.0, 2.0, 3.0, 10.0] {
+            let mut body = Body::new(Vector::ZERO, mass, force);
+            body.apply_force(force);
+            assert_close(body.acceleration(), force * (2.0 / mass));
+        }
+    }
+}
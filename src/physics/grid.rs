@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use crate::rectangle::Rectangle;
+
+/// Uniform grid broadphase, every key is stored in each cell its rectangle overlaps
+/// so a query only has to look at the cells around the queried rectangle.
+pub struct SpatialGrid<K> {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<K>>,
+}
+
+impl<K: Copy + Ord> SpatialGrid<K> {
+    pub fn new(cell_size: u32) -> SpatialGrid<K> {
+        return SpatialGrid {
+            cell_size: cell_size as f32,
+            cells: HashMap::new(),
+        };
+    }
+
+    fn cell(&self, coordinate: f32) -> i32 {
+        return (coordinate / self.cell_size).floor() as i32;
+    }
+
+    fn cells_of(&self, rectangle: &Rectangle) -> (RangeInclusive<i32>, RangeInclusive<i32>) {
+        let corners = rectangle.get_corners();
+
+        return (
+            self.cell(corners.top_left.x)..=self.cell(corners.bottom_right.x),
+            self.cell(corners.top_left.y)..=self.cell(corners.bottom_right.y),
+        );
+    }
+
+    pub fn insert(&mut self, key: K, rectangle: &Rectangle) {
+        let (columns, rows) = self.cells_of(rectangle);
+
+        for column in columns {
+            for row in rows.clone() {
+                self.cells.entry((column, row)).or_default().push(key);
+            }
+        }
+    }
+
+    /// Keys whose rectangle may overlap `rectangle`, sorted and without duplicates.
+    ///
+    /// Touching rectangles share a cell, so they are always part of the candidates.
+    pub fn query(&self, rectangle: &Rectangle) -> Vec<K> {
+        let (columns, rows) = self.cells_of(rectangle);
+        let mut keys = vec![];
+
+        for column in columns {
+            for row in rows.clone() {
+                if let Some(cell) = self.cells.get(&(column, row)) {
+                    keys.extend_from_slice(cell);
+                }
+            }
+        }
+
+        keys.sort();
+        keys.dedup();
+
+        return keys;
+    }
+}
@@ -1,54 +1,67 @@
-pub mod force;
-pub use force::Force;
+pub mod body;
+pub mod grid;
+pub mod vector;
+pub use body::Body;
+pub use grid::SpatialGrid;
+pub use vector::Vector;
 
 use crate::rectangle::Rectangle;
 
-#[derive(Clone, Copy, Debug)]
-pub struct Position {
-    pub x: i32,
-    pub y: i32,
-}
+pub type Position = Vector;
+pub type ChangingFactor = Vector;
 
-#[derive(Clone, Copy, Debug)]
-pub struct ChangingFactor {
-    pub x: f32,
-    pub y: f32,
+/// Linearly interpolates between two positions, `alpha` going from 0 (`previous`) to 1 (`current`).
+pub fn interpolate(previous: Position, current: Position, alpha: f32) -> Position {
+    return previous + (current - previous) * alpha;
 }
 
-pub fn next_position(
-    rectangle: Rectangle,
-    current_velocity: ChangingFactor,
-    forces: Vec<Force>,
-) -> Position {
-    let mut resultant_force = Force {
-        direction: ChangingFactor {
-            x: 0.0,
-            y: 0.0,
-        },
-        acceleration: ChangingFactor {
-            x: 0.0,
-            y: 0.0,
+/// Swept AABB test of `moving` travelling by `displacement` against a still `obstacle`.
+///
+/// Returns the fraction of the displacement (from 0 to 1) at which they first touch, 0 if
+/// they already overlap and `None` if they don't meet. Two moving rectangles can be tested
+/// by passing the difference of their displacements.
+pub fn sweep(moving: &Rectangle, displacement: Position, obstacle: &Rectangle) -> Option<f32> {
+    let moving_corners = moving.get_corners();
+    let obstacle_corners = obstacle.get_corners();
+
+    // Fractions of the displacement during which the rectangles overlap on one axis
+    let axis_overlap = |moving_start: f32, moving_end: f32, obstacle_start: f32, obstacle_end: f32, distance: f32| {
+        let enter_distance = obstacle_start - moving_end;
+        let exit_distance = obstacle_end - moving_start;
+
+        if distance == 0.0 {
+            if enter_distance <= 0.0 && exit_distance >= 0.0 {
+                return Some((f32::NEG_INFINITY, f32::INFINITY));
+            }
+            return None;
         }
+
+        let enter = enter_distance / distance;
+        let exit = exit_distance / distance;
+        return Some((enter.min(exit), enter.max(exit)));
     };
 
-    for &force in forces.iter() {
-        resultant_force.add_force(force);
-    }
+    let (x_enter, x_exit) = axis_overlap(
+        moving_corners.top_left.x,
+        moving_corners.bottom_right.x,
+        obstacle_corners.top_left.x,
+        obstacle_corners.bottom_right.x,
+        displacement.x,
+    )?;
+    let (y_enter, y_exit) = axis_overlap(
+        moving_corners.top_left.y,
+        moving_corners.bottom_right.y,
+        obstacle_corners.top_left.y,
+        obstacle_corners.bottom_right.y,
+        displacement.y,
+    )?;
 
-    let rectangle_size = Rectangle::to_rectangle_size(rectangle.size);
-
-    let next_pos = Position {
-        x: (
-            rectangle.position.x as f32
-            + current_velocity.x * resultant_force.direction.x
-            + (resultant_force.direction.x * resultant_force.acceleration.x) / rectangle_size.width as f32
-        ) as i32,
-        y: (
-            rectangle.position.y as f32
-            + current_velocity.y * resultant_force.direction.y
-            + (resultant_force.direction.y * resultant_force.acceleration.y) / rectangle_size.height as f32
-        ) as i32,
-    };
+    let enter = x_enter.max(y_enter).max(0.0);
+    let exit = x_exit.min(y_exit).min(1.0);
+
+    if enter > exit {
+        return None;
+    }
 
-    return next_pos;
-}
\ No newline at end of file
+    return Some(enter);
+}
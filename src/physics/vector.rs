@@ -0,0 +1,89 @@
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, Neg};
+
+use serde::{Serialize, Deserialize};
+
+/// A 2D vector of the simulation, in pixels for positions and pixels per tick for velocities.
+///
+/// Positions are only rounded to whole pixels when they are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Vector {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vector {
+    pub const ZERO: Vector = Vector { x: 0.0, y: 0.0 };
+
+    pub fn new(x: f32, y: f32) -> Vector {
+        return Vector { x, y };
+    }
+
+    pub fn length(self) -> f32 {
+        return self.x.hypot(self.y);
+    }
+
+    /// Angle from the x axis, in radians.
+    pub fn angle(self) -> f32 {
+        return self.y.atan2(self.x);
+    }
+
+    /// The vector rotated by `angle` radians.
+    pub fn rotated(self, angle: f32) -> Vector {
+        let (sin, cos) = angle.sin_cos();
+        return Vector::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos);
+    }
+
+    /// The vector scaled to a length of 1, the zero vector stays as it is.
+    pub fn normalized(self) -> Vector {
+        let length = self.length();
+        if length == 0.0 {
+            return Vector::ZERO;
+        }
+
+        return self * (1.0 / length);
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        return Vector::new(self.x + other.x, self.y + other.y);
+    }
+}
+
+impl AddAssign for Vector {
+    fn add_assign(&mut self, other: Vector) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        return Vector::new(self.x - other.x, self.y - other.y);
+    }
+}
+
+impl SubAssign for Vector {
+    fn sub_assign(&mut self, other: Vector) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<f32> for Vector {
+    type Output = Vector;
+
+    fn mul(self, factor: f32) -> Vector {
+        return Vector::new(self.x * factor, self.y * factor);
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        return Vector::new(-self.x, -self.y);
+    }
+}
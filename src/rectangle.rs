@@ -1,3 +1,5 @@
+use serde::{Serialize, Deserialize};
+
 use crate::physics::Position;
 
 pub struct RectangleCornersPositions {
@@ -7,19 +9,19 @@ pub struct RectangleCornersPositions {
     pub bottom_right: Position,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct RectangleSize {
     pub height: u32,
     pub width: u32,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Size {
     Square(u32),
     Rectangle(RectangleSize),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Rectangle {
     pub position: Position,
     pub size: Size,
@@ -48,49 +50,225 @@ impl Rectangle {
         let size = Rectangle::to_rectangle_size(self.size.clone());
         let corners = RectangleCornersPositions {
             top_left: Position {
-                x: self.position.x - size.width as i32 / 2,
-                y: self.position.y - size.height as i32 / 2,
+                x: self.position.x - size.width as f32 / 2.0,
+                y: self.position.y - size.height as f32 / 2.0,
             },
             top_right: Position {
-                x: self.position.x + size.width as i32 / 2,
-                y: self.position.y - size.height as i32 / 2,
+                x: self.position.x + size.width as f32 / 2.0,
+                y: self.position.y - size.height as f32 / 2.0,
             },
             bottom_left: Position {
-                x: self.position.x - size.width as i32 / 2,
-                y: self.position.y + size.height as i32 / 2,
+                x: self.position.x - size.width as f32 / 2.0,
+                y: self.position.y + size.height as f32 / 2.0,
             },
             bottom_right: Position {
-                x: self.position.x + size.width as i32 / 2,
-                y: self.position.y + size.height as i32 / 2,
+                x: self.position.x + size.width as f32 / 2.0,
+                y: self.position.y + size.height as f32 / 2.0,
             },
         };
         
         return corners;
     }
 
-    pub fn contains_position(&self, position: Position) -> bool {
+    /// Builds the rectangle starting at `left` and `top` with whole pixel sizes.
+    fn from_edges(left: f32, top: f32, width: u32, height: u32) -> Rectangle {
+        return Rectangle {
+            position: Position {
+                x: left + width as f32 / 2.0,
+                y: top + height as f32 / 2.0,
+            },
+            size: Size::Rectangle(RectangleSize {
+                width,
+                height,
+            }),
+        };
+    }
+
+    /// Whether both rectangles share at least one point, touching edges included.
+    pub fn intersects(&self, other: &Rectangle) -> bool {
         let corners = self.get_corners();
+        let other_corners = other.get_corners();
+
+        return corners.top_left.x <= other_corners.bottom_right.x
+            && other_corners.top_left.x <= corners.bottom_right.x
+            && corners.top_left.y <= other_corners.bottom_right.y
+            && other_corners.top_left.y <= corners.bottom_right.y;
+    }
 
-        if position.x > corners.top_left.x
-        && (position.x as i64) < corners.top_right.x as i64
-        && position.y > corners.top_left.y
-        && (position.y as i64) < corners.bottom_left.y as i64 {
-            return true;
+    /// The area shared by both rectangles, empty (zero sized) when they only touch.
+    ///
+    /// Sizes are whole pixels, so a fractional overlap is rounded down to keep the
+    /// result inside both rectangles.
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        if !self.intersects(other) {
+            return None;
         }
-        return false;
+
+        let corners = self.get_corners();
+        let other_corners = other.get_corners();
+
+        let left = corners.top_left.x.max(other_corners.top_left.x);
+        let top = corners.top_left.y.max(other_corners.top_left.y);
+        let right = corners.bottom_right.x.min(other_corners.bottom_right.x);
+        let bottom = corners.bottom_right.y.min(other_corners.bottom_right.y);
+
+        return Some(Rectangle::from_edges(
+            left,
+            top,
+            (right - left).floor() as u32,
+            (bottom - top).floor() as u32,
+        ));
     }
 
-    pub fn over(&self, outside_rectangle: Rectangle) -> bool {
+    /// Whether `other` lies entirely inside this rectangle, shared edges included.
+    pub fn contains(&self, other: &Rectangle) -> bool {
         let corners = self.get_corners();
+        let other_corners = other.get_corners();
+
+        return corners.top_left.x <= other_corners.top_left.x
+            && other_corners.bottom_right.x <= corners.bottom_right.x
+            && corners.top_left.y <= other_corners.top_left.y
+            && other_corners.bottom_right.y <= corners.bottom_right.y;
+    }
+
+    /// The smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rectangle) -> Rectangle {
+        let corners = self.get_corners();
+        let other_corners = other.get_corners();
+
+        let left = corners.top_left.x.min(other_corners.top_left.x);
+        let top = corners.top_left.y.min(other_corners.top_left.y);
+        let right = corners.bottom_right.x.max(other_corners.bottom_right.x);
+        let bottom = corners.bottom_right.y.max(other_corners.bottom_right.y);
+
+        // Sizes are whole pixels, a fractional extent is rounded up so both still fit
+        return Rectangle::from_edges(
+            left,
+            top,
+            (right - left).ceil() as u32,
+            (bottom - top).ceil() as u32,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rectangle(x: f32, y: f32, width: u32, height: u32) -> Rectangle {
+        return Rectangle {
+            position: Position { x, y },
+            size: Size::Rectangle(RectangleSize { width, height }),
+        };
+    }
+
+    /// Every rectangle with its center on a half pixel grid and a few sizes, flat ones included.
+    fn all_rectangles() -> Vec<Rectangle> {
+        let coordinates = [0.0, 2.5, 5.0, 7.5, 10.0, 12.5];
+        let sizes = [0, 1, 4, 10];
+
+        let mut rectangles = vec![];
+        for x in coordinates {
+            for y in coordinates {
+                for width in sizes {
+                    for height in sizes {
+                        rectangles.push(rectangle(x, y, width, height));
+                    }
+                }
+            }
+        }
+
+        return rectangles;
+    }
 
-        if outside_rectangle.contains_position(corners.top_left)
-        || outside_rectangle.contains_position(corners.top_right)
-        || outside_rectangle.contains_position(corners.bottom_left)
-        || outside_rectangle.contains_position(corners.bottom_right) {
-            return true;
+    #[test]
+    fn intersects_is_symmetric() {
+        let rectangles = all_rectangles();
+        for a in rectangles.iter() {
+            for b in rectangles.iter() {
+                assert_eq!(a.intersects(b), b.intersects(a), "{:?} and {:?}", a, b);
+            }
         }
+    }
+
+    #[test]
+    fn touching_edges_intersect() {
+        let a = rectangle(5.0, 5.0, 10, 10);
+
+        assert!(a.intersects(&rectangle(15.0, 5.0, 10, 10)));
+        assert!(a.intersects(&rectangle(5.0, 15.0, 10, 10)));
+        assert!(a.intersects(&rectangle(15.0, 15.0, 10, 10)));
+        assert!(!a.intersects(&rectangle(15.5, 5.0, 10, 10)));
+
+        let intersection = a.intersection(&rectangle(15.0, 5.0, 10, 10)).unwrap();
+        let size = Rectangle::to_rectangle_size(intersection.size);
+        assert_eq!(size.width, 0);
+        assert_eq!(size.height, 10);
+    }
+
+    #[test]
+    fn thin_missile_through_asteroid_middle() {
+        let asteroid = rectangle(100.0, 100.0, 80, 80);
+        let missile = rectangle(100.0, 100.0, 15, 0);
+
+        assert!(asteroid.intersects(&missile));
+        assert!(missile.intersects(&asteroid));
+        assert!(asteroid.contains(&asteroid.intersection(&missile).unwrap()));
+    }
+
+    #[test]
+    fn cross_overlap_without_corner_inside() {
+        let tall = rectangle(50.0, 50.0, 10, 100);
+        let wide = rectangle(50.0, 50.0, 100, 10);
+
+        assert!(tall.intersects(&wide));
+        assert!(wide.intersects(&tall));
 
-        return false;
+        let intersection = tall.intersection(&wide).unwrap();
+        assert_eq!(intersection.position, Position { x: 50.0, y: 50.0 });
+        let size = Rectangle::to_rectangle_size(intersection.size);
+        assert_eq!(size.width, 10);
+        assert_eq!(size.height, 10);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn contains_includes_shared_edges() {
+        let outer = rectangle(50.0, 50.0, 100, 100);
+
+        assert!(outer.contains(&outer));
+        assert!(outer.contains(&rectangle(5.0, 50.0, 10, 100)));
+        assert!(!outer.contains(&rectangle(4.5, 50.0, 10, 10)));
+        assert!(!rectangle(50.0, 50.0, 10, 10).contains(&outer));
+    }
+
+    #[test]
+    fn intersection_is_inside_both() {
+        let rectangles = all_rectangles();
+        for a in rectangles.iter() {
+            for b in rectangles.iter() {
+                match a.intersection(b) {
+                    Some(intersection) => {
+                        assert!(a.intersects(b), "{:?} and {:?}", a, b);
+                        assert!(a.contains(&intersection), "{:?} and {:?}", a, b);
+                        assert!(b.contains(&intersection), "{:?} and {:?}", a, b);
+                    }
+                    None => assert!(!a.intersects(b), "{:?} and {:?}", a, b),
+                }
+            }
+        }
+
+        assert!(rectangle(0.0, 0.0, 4, 4).intersection(&rectangle(10.0, 0.0, 4, 4)).is_none());
+    }
+
+    #[test]
+    fn union_contains_both() {
+        let rectangles = all_rectangles();
+        for a in rectangles.iter() {
+            for b in rectangles.iter() {
+                let union = a.union(b);
+                assert!(union.contains(a), "{:?} and {:?}", a, b);
+                assert!(union.contains(b), "{:?} and {:?}", a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use std::fmt;
+use std::fs;
+use std::collections::VecDeque;
+use std::sync::mpsc::Receiver;
+
+use crate::helper::{U2GMessage, InputSource};
+use crate::missile::MissileType;
+use crate::physics::Position;
+use crate::settings;
+
+#[derive(Clone, Copy, Debug)]
+pub struct TimedInput {
+    pub tick: u64,
+    pub message: U2GMessage,
+}
+
+/// A seed and the inputs consumed on each tick, enough to reproduce a whole run.
+///
+/// The ticks count every iteration of the game loop since launch, so they keep going while
+/// the game is paused and across restarts, unlike `Game::current_tick`.
+///
+/// The file has an optional `seed <number>` line, an optional `settings <hash>` line (the
+/// `Settings::fingerprint` it was recorded with, in hexadecimal) and an optional `title-menu`
+/// line (the run started on the title menu) followed by one `<tick> <command> [argument]` per line,
+/// in tick order.
+/// Commands: `move <y>`, `shoot <missile>`, `release <missile>` (a missile kind name:
+/// `normal`, `bomb`, `homing`, `piercing`, `spread` or `mine`), `laser`, `release-laser`,
+/// `pause`, `menu-up`, `menu-down`, `menu-select`, `menu-hover <item>`, `menu-click <item>`,
+/// `back`, `debug`, `generation-line`, `rows`, `game-state`, `invincible`, `generation`,
+/// `quick-save`, `quick-load`, `restart`, `quit` and `close`. Lines starting with `#` are ignored.
+#[derive(Clone, Debug)]
+pub struct Replay {
+    pub seed: Option<u64>,
+    pub settings: Option<u64>,
+    pub title_menu: bool,
+    pub inputs: Vec<TimedInput>,
+}
+
+fn parse_missile_type(value: Option<&str>) -> Result<MissileType, String> {
+    let value = value.ok_or("Missing missile type")?;
+
+    return MissileType::from_name(value).ok_or(format!("Unknown missile type: {}", value));
+}
+
+fn parse_item_index(value: Option<&str>) -> Result<usize, String> {
+    let value = value.ok_or("Missing menu item")?;
+
+    return value
+        .parse()
+        .map_err(|_| format!("Invalid menu item: {}", value));
+}
+
+fn format_message(message: U2GMessage) -> String {
+    match message {
+        U2GMessage::MouseMotion(position) => format!("move {}", position.y),
+        U2GMessage::StartShooting(missile_type) => format!("shoot {}", missile_type.name()),
+        U2GMessage::StopShooting(missile_type) => format!("release {}", missile_type.name()),
+        U2GMessage::StartLaser => String::from("laser"),
+        U2GMessage::StopLaser => String::from("release-laser"),
+        U2GMessage::TogglePause => String::from("pause"),
+        U2GMessage::MenuUp => String::from("menu-up"),
+        U2GMessage::MenuDown => String::from("menu-down"),
+        U2GMessage::MenuSelect => String::from("menu-select"),
+        U2GMessage::MenuHover(item_index) => format!("menu-hover {}", item_index),
+        U2GMessage::MenuClick(item_index) => format!("menu-click {}", item_index),
+        U2GMessage::Back => String::from("back"),
+        U2GMessage::ToggleDebugging => String::from("debug"),
+        U2GMessage::ToggleGenerationLine => String::from("generation-line"),
+        U2GMessage::ToggleRows => String::from("rows"),
+        U2GMessage::ToggleGameState => String::from("game-state"),
+        U2GMessage::ToggleInvincible => String::from("invincible"),
+        U2GMessage::ToggleAsteroidGeneration => String::from("generation"),
+        U2GMessage::QuickSave => String::from("quick-save"),
+        U2GMessage::QuickLoad => String::from("quick-load"),
+        U2GMessage::Restart => String::from("restart"),
+        U2GMessage::Quit => String::from("quit"),
+        U2GMessage::Close => String::from("close"),
+    }
+}
+
+fn parse_message(command: &str, argument: Option<&str>) -> Result<U2GMessage, String> {
+    let message = match command {
+        "move" => {
+            let y = argument.ok_or("Missing y position")?;
+            let y: f32 = y
+                .parse()
+                .map_err(|_| format!("Invalid y position: {}", y))?;
+            U2GMessage::MouseMotion(Position { x: 0.0, y })
+        }
+        "shoot" => U2GMessage::StartShooting(parse_missile_type(argument)?),
+        "release" => U2GMessage::StopShooting(parse_missile_type(argument)?),
+        "laser" => U2GMessage::StartLaser,
+        "release-laser" => U2GMessage::StopLaser,
+        "pause" => U2GMessage::TogglePause,
+        "menu-up" => U2GMessage::MenuUp,
+        "menu-down" => U2GMessage::MenuDown,
+        "menu-select" => U2GMessage::MenuSelect,
+        "menu-hover" => U2GMessage::MenuHover(parse_item_index(argument)?),
+        "menu-click" => U2GMessage::MenuClick(parse_item_index(argument)?),
+        "back" => U2GMessage::Back,
+        "debug" => U2GMessage::ToggleDebugging,
+        "generation-line" => U2GMessage::ToggleGenerationLine,
+        "rows" => U2GMessage::ToggleRows,
+        "game-state" => U2GMessage::ToggleGameState,
+        "invincible" => U2GMessage::ToggleInvincible,
+        "generation" => U2GMessage::ToggleAsteroidGeneration,
+        "quick-save" => U2GMessage::QuickSave,
+        "quick-load" => U2GMessage::QuickLoad,
+        "restart" => U2GMessage::Restart,
+        "quit" => U2GMessage::Quit,
+        "close" => U2GMessage::Close,
+        _ => return Err(format!("Unknown command: {}", command)),
+    };
+
+    return Ok(message);
+}
+
+impl Replay {
+    pub fn new(seed: Option<u64>, title_menu: bool) -> Replay {
+        return Replay {
+            seed,
+            settings: None,
+            title_menu,
+            inputs: vec![],
+        };
+    }
+
+    pub fn parse(source: &str) -> Result<Replay, String> {
+        let mut replay = Replay::new(None, false);
+
+        for (line_i, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |message: String| format!("Line {}: {}", line_i + 1, message);
+            let mut words = line.split_whitespace();
+
+            let first_word = words.next().unwrap();
+            if first_word == "seed" {
+                let seed = words.next().ok_or_else(|| error(String::from("Missing seed")))?;
+                replay.seed = Some(seed
+                    .parse()
+                    .map_err(|_| error(format!("Invalid seed: {}", seed)))?);
+                continue;
+            }
+            if first_word == "settings" {
+                let hash = words.next().ok_or_else(|| error(String::from("Missing settings hash")))?;
+                replay.settings = Some(u64::from_str_radix(hash, 16)
+                    .map_err(|_| error(format!("Invalid settings hash: {}", hash)))?);
+                continue;
+            }
+            if first_word == "title-menu" {
+                replay.title_menu = true;
+                continue;
+            }
+
+            let tick: u64 = first_word
+                .parse()
+                .map_err(|_| error(format!("Invalid tick: {}", first_word)))?;
+            // The player hands the inputs out in order, an earlier one would be delivered late
+            if let Some(previous) = replay.inputs.last() {
+                if tick < previous.tick {
+                    return Err(error(format!("Tick {} comes after tick {}", tick, previous.tick)));
+                }
+            }
+            let command = words.next().ok_or_else(|| error(String::from("Missing command")))?;
+            let message = parse_message(command, words.next()).map_err(error)?;
+
+            replay.inputs.push(TimedInput { tick, message });
+        }
+
+        return Ok(replay);
+    }
+
+    pub fn load(path: &str) -> Result<Replay, String> {
+        let source = fs::read_to_string(path)
+            .map_err(|error| format!("Failed to read {}: {}", path, error))?;
+
+        return Replay::parse(&source).map_err(|error| format!("{}: {}", path, error));
+    }
+
+    /// Whether it was recorded with the settings in use, replays without a hash are trusted.
+    pub fn matches_settings(&self) -> bool {
+        return self.settings.is_none_or(|hash| hash == settings::get().fingerprint());
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        return fs::write(path, self.to_string())
+            .map_err(|error| format!("Failed to write {}: {}", path, error));
+    }
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(seed) = self.seed {
+            writeln!(f, "seed {}", seed)?;
+        }
+        if let Some(hash) = self.settings {
+            writeln!(f, "settings {:016x}", hash)?;
+        }
+        if self.title_menu {
+            writeln!(f, "title-menu")?;
+        }
+
+        for input in self.inputs.iter() {
+            writeln!(f, "{} {}", input.tick, format_message(input.message))?;
+        }
+
+        return Ok(());
+    }
+}
+
+/// Feeds the inputs of a replay back to the game on the ticks they were recorded.
+///
+/// When a live channel is given only its `Close` messages go through, so the window
+/// can still be closed while watching a replay.
+pub struct Player {
+    inputs: VecDeque<TimedInput>,
+    live: Option<Receiver<U2GMessage>>,
+    tick: u64,
+}
+
+impl Player {
+    pub fn new(replay: Replay, live: Option<Receiver<U2GMessage>>) -> Player {
+        return Player {
+            inputs: replay.inputs.into_iter().collect(),
+            live,
+            tick: 0,
+        };
+    }
+}
+
+impl InputSource for Player {
+    fn inputs(&mut self) -> Vec<U2GMessage> {
+        let mut messages = vec![];
+
+        while let Some(input) = self.inputs.front() {
+            if input.tick > self.tick {
+                break;
+            }
+            messages.push(input.message);
+            self.inputs.pop_front();
+        }
+        self.tick += 1;
+
+        if let Some(live) = &self.live {
+            for message in live.try_iter() {
+                if let U2GMessage::Close = message {
+                    messages.push(message);
+                }
+            }
+        }
+
+        return messages;
+    }
+}
+
+/// Records every input the game consumes from the wrapped source.
+pub struct Recorder<S: InputSource> {
+    source: S,
+    pub replay: Replay,
+    tick: u64,
+}
+
+impl<S: InputSource> Recorder<S> {
+    pub fn new(source: S, seed: u64, title_menu: bool) -> Recorder<S> {
+        let mut replay = Replay::new(Some(seed), title_menu);
+        replay.settings = Some(settings::get().fingerprint());
+
+        return Recorder {
+            source,
+            replay,
+            tick: 0,
+        };
+    }
+}
+
+impl<S: InputSource> InputSource for Recorder<S> {
+    fn inputs(&mut self) -> Vec<U2GMessage> {
+        let messages = self.source.inputs();
+
+        for &message in messages.iter() {
+            self.replay.inputs.push(TimedInput { tick: self.tick, message });
+        }
+        self.tick += 1;
+
+        return messages;
+    }
+}
@@ -0,0 +1,134 @@
+use serde::{Serialize, Deserialize};
+
+use crate::game::Game;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Scene {
+    TitleMenu,
+    Options,
+    HighScores,
+    InGame,
+    PauseMenu,
+    GameOver,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MenuItem {
+    Play,
+    Options,
+    HighScores,
+    Resume,
+    Restart,
+    MainMenu,
+    Debugging,
+    Back,
+    Quit,
+}
+
+impl Scene {
+    pub fn menu_items(&self) -> &'static [MenuItem] {
+        match self {
+            Scene::TitleMenu => &[MenuItem::Play, MenuItem::Options, MenuItem::HighScores, MenuItem::Quit],
+            Scene::Options => &[MenuItem::Debugging, MenuItem::Back],
+            Scene::HighScores => &[MenuItem::Back],
+            Scene::InGame => &[],
+            Scene::PauseMenu => &[MenuItem::Resume, MenuItem::Restart, MenuItem::MainMenu, MenuItem::Quit],
+            Scene::GameOver => &[MenuItem::Restart, MenuItem::MainMenu, MenuItem::Quit],
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Scene::TitleMenu => "FLOOTING",
+            Scene::Options => "OPTIONS",
+            Scene::HighScores => "HIGH SCORES",
+            Scene::InGame => "",
+            Scene::PauseMenu => "PAUSED",
+            Scene::GameOver => "GAME OVER",
+        }
+    }
+
+    /// Overlays are drawn on top of the (frozen) game instead of the bare background.
+    pub fn is_overlay(&self) -> bool {
+        matches!(self, Scene::InGame | Scene::PauseMenu | Scene::GameOver)
+    }
+}
+
+impl MenuItem {
+    pub fn label(&self, game: &Game) -> String {
+        let on_off = |value: bool| if value { "On" } else { "Off" };
+
+        match self {
+            MenuItem::Play => String::from("Play"),
+            MenuItem::Options => String::from("Options"),
+            MenuItem::HighScores => String::from("High scores"),
+            MenuItem::Resume => String::from("Resume"),
+            MenuItem::Restart => String::from("Restart"),
+            MenuItem::MainMenu => String::from("Main menu"),
+            MenuItem::Debugging => format!("Debug mode: {}", on_off(game.debugging)),
+            MenuItem::Back => String::from("Back"),
+            MenuItem::Quit => String::from("Quit"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SceneStack {
+    stack: Vec<Scene>,
+    pub selected_item: usize,
+}
+
+impl SceneStack {
+    pub fn new(scene: Scene) -> SceneStack {
+        return SceneStack {
+            stack: vec![scene],
+            selected_item: 0,
+        };
+    }
+
+    pub fn current(&self) -> Scene {
+        return self.stack[self.stack.len() - 1];
+    }
+
+    pub fn push(&mut self, scene: Scene) {
+        self.stack.push(scene);
+        self.selected_item = 0;
+    }
+
+    /// Goes back to the previous scene, the bottom one is never popped.
+    pub fn pop(&mut self) {
+        if self.stack.len() > 1 {
+            self.stack.pop();
+            self.selected_item = 0;
+        }
+    }
+
+    pub fn reset(&mut self, scene: Scene) {
+        self.stack = vec![scene];
+        self.selected_item = 0;
+    }
+
+    pub fn select(&mut self, item_index: usize) {
+        if item_index < self.current().menu_items().len() {
+            self.selected_item = item_index;
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        let items_count = self.current().menu_items().len();
+        if items_count > 0 {
+            self.selected_item = (self.selected_item + 1) % items_count;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        let items_count = self.current().menu_items().len();
+        if items_count > 0 {
+            self.selected_item = (self.selected_item + items_count - 1) % items_count;
+        }
+    }
+
+    pub fn selected(&self) -> Option<MenuItem> {
+        return self.current().menu_items().get(self.selected_item).copied();
+    }
+}
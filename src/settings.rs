@@ -1,116 +1,644 @@
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+use std::sync::{Arc, OnceLock, RwLock};
+
 use sdl2::pixels::Color;
+use sdl2::rect::Point;
+use serde::{Serialize, Deserialize};
 
 use crate::physics::Position;
 
-use crate::time;
 use crate::game::{DebugOptions};
+use crate::missile::missiles::Spread;
+use crate::ammo::MAX_HEAT;
 use crate::rectangle::{Rectangle, Size, RectangleSize};
 
-// Window
-pub const WINDOW_TITLE: &str = "Flooting";
-pub const WINDOW_WIDTH: u32 = 1200;
-// pub const WINDOW_HEIGHT: u32 = 600;
-pub const WINDOW_HEIGHT: u32 = 845;
-pub const FPS: u32 = 60;
-
-// NextGen
-pub const NEXT_GENERATION_DELAY: u128 = time::to_nano(2000);
-
-// Sprites
-pub const SPRITES_FILE_PATH: &str = "./assets/sprites/sprite.png";
-pub const ASTEROID_SPRITE_RECTANGLE: Rectangle = Rectangle {
-    position: Position {
-        x: 0,
-        y: 0,
-    },
-    size: Size::Rectangle(RectangleSize {
-        width: 285,
-        height: 246,
-    })
-};
-pub const SPACESHIP_SPRITE_RECTANGLE: Rectangle = Rectangle {
-    position: Position {
-        x: 0,
-        y: 250,
-    },
-    size: Size::Rectangle(RectangleSize {
-        width: 241,
-        height: 209,
-    })
-};
-pub const MISSILE_SPRITE_RECTANGLE: Rectangle = Rectangle {
-    position: Position {
-        x: 0,
-        y: 460,
-    },
-    size: Size::Rectangle(RectangleSize {
-        width: 254,
-        height: 67,
-    })
-};
-pub const BACKGROUND_SPRITE_RECTANGLE: Rectangle = Rectangle {
-    position: Position {
-        x: 287,
-        y: 0,
-    },
-    size: Size::Rectangle(RectangleSize {
-        width: 852,
-        height: 480,
-    }),
-};
-
-// Debugging
-pub const DEBUG_FONT_PATH: &str = "./assets/fonts/debug.ttf";
-pub const DEBUG_FONT_POINT_SIZE: u16 = 15;
-pub const DEBUG: bool = false;
-pub const DEBUG_COLOR: Color = Color::MAGENTA;
-pub const DEFAULT_DEBUG_OPTIONS: DebugOptions = DebugOptions {
-    generation_line: true,
-    rows: true,
-    game_state: true,
-    invincible: true,
-    asteroid_generation: true,
-};
-
-// Spaceship
-pub const SPACESHIP_WIDTH: u32 = 32;
-pub const SPACESHIP_HEIGHT: u32 = 42;
-pub const SPACESHIP_LIFE: u8 = 100;
-pub const LIFE_COLOR: Color = sdl2::pixels::Color::RGB(0, 255, 21);
-pub const INITIAL_SPACESHIP_POSITION: Position = Position {
-    x: 40,
-    y: ((WINDOW_HEIGHT / 2) - (SPACESHIP_HEIGHT / 2)) as i32,
-};
-
-// Missile
-pub const MISSILE_WIDTH: u32 = 15;
-pub const MISSILE_HEIGHT: u32 = 10;
-pub const SHOTS_PER_SECOND: u16 = 10;
-pub const MISSILE_COLOR: Color = Color::YELLOW;
-
-// Pixels
-pub const PIXELS_MULTIPLIER_FACTOR: u32 = 22;
-
-// Asteroid
-pub const GENERATE_NEW_ASTEROID_AFTER: u32 = 100;
-pub const ALLOW_INSIDE_GENERATION: bool = false;
-pub const MIN_GENERATED_ASTEROIDS: u8 = 1;
-pub const MAX_GENERATED_ASTEROIDS: u8 = 1;
-pub const MIN_ASTEROIDS_SPEED: u32 = 2;
-pub const MAX_ASTEROIDS_SPEED: u32 = 3;
-pub const MIN_ASTEROIDS_SIZE: u8 = 2;
-pub const MAX_ASTEROIDS_SIZE: u8 = 3;
-pub const ASTEROIDS_ROWS_HEIGHT: u32 = WINDOW_HEIGHT as u32 / ASTEROIDS_ROWS as u32;
-
-pub const ASTEROIDS_ROWS_PADDING: u32 = 25;
-pub const ASTEROIDS_ROWS: usize = (
-    WINDOW_HEIGHT /
-    (
-        MAX_ASTEROIDS_SIZE as u32 * PIXELS_MULTIPLIER_FACTOR
-        + ASTEROIDS_ROWS_PADDING
-    )
-) as usize;
-pub const ASTEROIDS_MARGIN: Position = Position {
-    x: 100,
-    y: 0,
-};
\ No newline at end of file
+// Settings file
+pub const SETTINGS_FILE_PATH: &str = "./settings.ron";
+
+// Headless
+pub const HEADLESS_SECONDS: u64 = 60;
+
+// High scores
+pub const DATA_DIRECTORY_NAME: &str = "flooting";
+pub const HIGH_SCORES_FILE_NAME: &str = "highscores.ron";
+pub const HIGH_SCORES_COUNT: usize = 10;
+
+// Snapshots
+pub const QUICK_SAVE_FILE_PATH: &str = "./quicksave.ron";
+
+// Menus
+pub const MENU_ITEM_WIDTH: u32 = 300;
+pub const MENU_ITEM_HEIGHT: u32 = 40;
+/// Room left for the menu items at the bottom of the window.
+pub const MENU_ITEMS_HEIGHT: u32 = 250;
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Color")]
+struct ColorDef {
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+}
+
+/// Every tuning value of the game, loaded from `settings.ron` when it exists.
+///
+/// Missing keys keep their default value, so the file only needs what was changed.
+/// Delays are in milliseconds. See `with_tuning_of` for the ones that can be reloaded while the game runs.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    // Window
+    pub window_title: String,
+    pub window_width: u32,
+    pub window_height: u32,
+    pub fullscreen: bool,
+    pub fps: u32,
+
+    // Simulation
+    pub ticks_per_second: u32,
+    pub max_ticks_per_frame: u32,
+
+    // NextGen
+    pub starting_generation: u32,
+    pub next_generation_delay: u16,
+
+    // Sprites
+    pub sprites_file_path: String,
+    pub asteroid_sprite_rectangle: Rectangle,
+    pub spaceship_sprite_rectangle: Rectangle,
+    pub missile_sprite_rectangle: Rectangle,
+    pub background_sprite_rectangle: Rectangle,
+
+    // HUD
+    pub hud_font_path: String,
+    pub hud_font_point_size: u16,
+
+    // Menus
+    #[serde(with = "ColorDef")]
+    pub menu_color: Color,
+    #[serde(with = "ColorDef")]
+    pub menu_highlight_color: Color,
+    #[serde(with = "ColorDef")]
+    pub menu_background_color: Color,
+
+    // Debugging
+    pub debug_font_path: String,
+    pub debug_font_point_size: u16,
+    pub debug: bool,
+    #[serde(with = "ColorDef")]
+    pub debug_color: Color,
+    pub default_debug_options: DebugOptions,
+
+    // Spaceship
+    pub spaceship_width: u32,
+    pub spaceship_height: u32,
+    pub spaceship_life: u8,
+    #[serde(with = "ColorDef")]
+    pub life_color: Color,
+    pub initial_spaceship_x: f32,
+    /// Damage of a crash, per asteroid size and per pixel per tick of relative speed.
+    pub crash_damage_per_size: f32,
+    pub crash_damage_per_speed: f32,
+    pub invulnerability_delay: u16,
+    /// Speed the spaceship is pushed away at, in pixels per tick, and how much of it is
+    /// kept from one tick to the next.
+    pub crash_knockback: f32,
+    pub knockback_damping: f32,
+
+    // Missile
+    pub missile_width: u32,
+    pub missile_height: u32,
+    pub normal_missile_delay: u16,
+    pub bomb_missile_delay: u16,
+    pub homing_missile_delay: u16,
+    /// Most a homing missile can turn towards its target, in degrees per tick.
+    pub homing_turn_rate: f32,
+    /// Time before a homing missile that hit nothing burns out, in milliseconds.
+    pub homing_missile_lifetime: u16,
+    /// Asteroids within it around a bomb hit are damaged, the further the less.
+    pub bomb_blast_radius: f32,
+    pub bomb_damage: u8,
+    pub piercing_missile_delay: u16,
+    pub spread_missile_delay: u16,
+    /// Missiles fired at once by the spread shot at each weapon level.
+    pub spread_shot_fans: [u8; 3],
+    pub points_per_weapon_level: u64,
+    pub mine_delay: u16,
+    /// Time before a dropped mine can go off, and before it's gone if nothing came close.
+    pub mine_arming_delay: u16,
+    pub mine_lifetime: u16,
+    /// Distance from an asteroid that sets a mine off, in pixels.
+    pub mine_trigger_radius: f32,
+    /// Asteroids within it are damaged by the blast, from `mine_damage` at its center
+    /// down to nothing at its edge.
+    pub mine_blast_radius: f32,
+    pub mine_damage: u8,
+    pub max_active_mines: u8,
+    /// Rounds of the weapons with limited ammo, they're all refilled on every new generation.
+    pub bomb_charges: u16,
+    pub homing_ammo: u16,
+    pub mine_ammo: u16,
+    /// Time to get one round back while the weapon isn't full, in milliseconds.
+    pub homing_reload_delay: u16,
+    pub mine_reload_delay: u16,
+    /// Heat added by each normal missile, the gun overheats at `MAX_HEAT` and stays
+    /// locked until it cooled down to `overheat_recovery`.
+    pub normal_heat_per_shot: f32,
+    /// Heat lost per second.
+    pub heat_cooling: f32,
+    pub overheat_recovery: f32,
+    #[serde(with = "ColorDef")]
+    pub missile_color: Color,
+
+    // Laser
+    /// Energy of a full laser, firing drains `laser_drain` per second and releasing it
+    /// recharges `laser_recharge` per second.
+    pub laser_energy: f32,
+    pub laser_drain: f32,
+    pub laser_recharge: f32,
+    /// Once empty the laser can't fire until it recharged to this energy or it's released.
+    pub laser_recovery: f32,
+    /// Asteroid sizes taken off per second by the beam.
+    pub laser_damage: f32,
+    pub laser_width: u32,
+    #[serde(with = "ColorDef")]
+    pub laser_color: Color,
+
+    // Score
+    pub hit_points: u64,
+    pub destroy_points: u64,
+    pub hits_per_multiplier: u32,
+    pub max_score_multiplier: u32,
+    pub combo_decay_delay: u16,
+    #[serde(with = "ColorDef")]
+    pub score_color: Color,
+
+    // Pixels
+    pub pixels_multiplier_factor: u32,
+
+    // Physics
+    pub collision_grid_cell_size: u32,
+
+    // Asteroid
+    pub generate_new_asteroid_after: u32,
+    pub allow_inside_generation: bool,
+    pub min_generated_asteroids: u8,
+    pub max_generated_asteroids: u8,
+    pub min_asteroids_speed: u32,
+    pub max_asteroids_speed: u32,
+    pub min_asteroids_size: u8,
+    pub max_asteroids_size: u8,
+    pub asteroids_rows_padding: u32,
+    pub asteroids_margin: Position,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        return Settings {
+            window_title: String::from("Flooting"),
+            window_width: 1200,
+            window_height: 845,
+            fullscreen: false,
+            fps: 60,
+
+            ticks_per_second: 60,
+            max_ticks_per_frame: 5,
+
+            starting_generation: 1,
+            next_generation_delay: 2000,
+
+            sprites_file_path: String::from("./assets/sprites/sprite.png"),
+            asteroid_sprite_rectangle: Rectangle {
+                position: Position {
+                    x: 0.0,
+                    y: 0.0,
+                },
+                size: Size::Rectangle(RectangleSize {
+                    width: 285,
+                    height: 246,
+                })
+            },
+            spaceship_sprite_rectangle: Rectangle {
+                position: Position {
+                    x: 0.0,
+                    y: 250.0,
+                },
+                size: Size::Rectangle(RectangleSize {
+                    width: 241,
+                    height: 209,
+                })
+            },
+            missile_sprite_rectangle: Rectangle {
+                position: Position {
+                    x: 0.0,
+                    y: 460.0,
+                },
+                size: Size::Rectangle(RectangleSize {
+                    width: 254,
+                    height: 67,
+                })
+            },
+            background_sprite_rectangle: Rectangle {
+                position: Position {
+                    x: 287.0,
+                    y: 0.0,
+                },
+                size: Size::Rectangle(RectangleSize {
+                    width: 852,
+                    height: 480,
+                }),
+            },
+
+            hud_font_path: String::from("./assets/fonts/debug.ttf"),
+            hud_font_point_size: 20,
+
+            menu_color: Color::WHITE,
+            menu_highlight_color: Color::YELLOW,
+            menu_background_color: Color::RGBA(0, 0, 0, 190),
+
+            debug_font_path: String::from("./assets/fonts/debug.ttf"),
+            debug_font_point_size: 15,
+            debug: false,
+            debug_color: Color::MAGENTA,
+            default_debug_options: DebugOptions {
+                generation_line: true,
+                rows: true,
+                game_state: true,
+                invincible: true,
+                asteroid_generation: true,
+            },
+
+            spaceship_width: 32,
+            spaceship_height: 42,
+            spaceship_life: 100,
+            life_color: Color::RGB(0, 255, 21),
+            initial_spaceship_x: 40.0,
+            crash_damage_per_size: 3.0,
+            crash_damage_per_speed: 2.0,
+            invulnerability_delay: 1000,
+            crash_knockback: 12.0,
+            knockback_damping: 0.8,
+
+            missile_width: 15,
+            missile_height: 10,
+            normal_missile_delay: 1000 / 15,
+            bomb_missile_delay: 1000 / 10,
+            homing_missile_delay: 1000 / 4,
+            homing_turn_rate: 4.0,
+            homing_missile_lifetime: 3000,
+            bomb_blast_radius: 60.0,
+            bomb_damage: 2,
+            piercing_missile_delay: 300,
+            spread_missile_delay: 400,
+            spread_shot_fans: [3, 5, 7],
+            points_per_weapon_level: 1500,
+            mine_delay: 1000,
+            mine_arming_delay: 500,
+            mine_lifetime: 8000,
+            mine_trigger_radius: 40.0,
+            mine_blast_radius: 90.0,
+            mine_damage: 3,
+            max_active_mines: 3,
+            bomb_charges: 5,
+            homing_ammo: 6,
+            mine_ammo: 4,
+            homing_reload_delay: 2000,
+            mine_reload_delay: 3000,
+            normal_heat_per_shot: 6.0,
+            heat_cooling: 25.0,
+            overheat_recovery: 30.0,
+            missile_color: Color::YELLOW,
+
+            laser_energy: 100.0,
+            laser_drain: 50.0,
+            laser_recharge: 20.0,
+            laser_recovery: 25.0,
+            laser_damage: 6.0,
+            laser_width: 4,
+            laser_color: Color::RGB(255, 80, 80),
+
+            hit_points: 10,
+            destroy_points: 50,
+            hits_per_multiplier: 5,
+            max_score_multiplier: 8,
+            combo_decay_delay: 1500,
+            score_color: Color::WHITE,
+
+            pixels_multiplier_factor: 22,
+
+            collision_grid_cell_size: 64,
+
+            generate_new_asteroid_after: 100,
+            allow_inside_generation: false,
+            min_generated_asteroids: 1,
+            max_generated_asteroids: 1,
+            min_asteroids_speed: 2,
+            max_asteroids_speed: 3,
+            min_asteroids_size: 2,
+            max_asteroids_size: 3,
+            asteroids_rows_padding: 25,
+            asteroids_margin: Position {
+                x: 100.0,
+                y: 0.0,
+            },
+        };
+    }
+}
+
+impl Settings {
+    pub fn asteroids_rows(&self) -> usize {
+        return (
+            self.window_height /
+            (
+                self.max_asteroids_size as u32 * self.pixels_multiplier_factor
+                + self.asteroids_rows_padding
+            )
+        ) as usize;
+    }
+
+    pub fn asteroids_rows_height(&self) -> u32 {
+        return self.window_height / self.asteroids_rows() as u32;
+    }
+
+    pub fn initial_spaceship_position(&self) -> Position {
+        return Position {
+            x: self.initial_spaceship_x,
+            y: ((self.window_height / 2) - (self.spaceship_height / 2)) as f32,
+        };
+    }
+
+    pub fn menu_text_x(&self) -> i32 {
+        return self.window_width as i32 / 2 - 260;
+    }
+
+    pub fn menu_title_y(&self) -> i32 {
+        return self.window_height as i32 / 8;
+    }
+
+    pub fn menu_line_height(&self) -> u16 {
+        return self.hud_font_point_size + 10;
+    }
+
+    pub fn menu_items_y(&self) -> i32 {
+        return self.window_height as i32 - MENU_ITEMS_HEIGHT as i32;
+    }
+
+    pub fn score_position(&self) -> Point {
+        return Point::new(self.window_width as i32 - 180, 10);
+    }
+
+    /// Hash of every value, replays keep it to tell when they're played with other settings.
+    pub fn fingerprint(&self) -> u64 {
+        let serialized = ron::to_string(self).unwrap();
+
+        // FNV-1a, which unlike the std hashers gives the same hash on every build
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in serialized.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+
+        return hash;
+    }
+
+    /// Takes the tuning values of `reloaded` and keeps the rest, which needs a restart to change.
+    pub fn with_tuning_of(&self, reloaded: &Settings) -> Settings {
+        let mut settings = self.clone();
+
+        settings.next_generation_delay = reloaded.next_generation_delay;
+
+        settings.crash_damage_per_size = reloaded.crash_damage_per_size;
+        settings.crash_damage_per_speed = reloaded.crash_damage_per_speed;
+        settings.invulnerability_delay = reloaded.invulnerability_delay;
+        settings.crash_knockback = reloaded.crash_knockback;
+        settings.knockback_damping = reloaded.knockback_damping;
+
+        settings.menu_color = reloaded.menu_color;
+        settings.menu_highlight_color = reloaded.menu_highlight_color;
+        settings.menu_background_color = reloaded.menu_background_color;
+        settings.debug_color = reloaded.debug_color;
+        settings.life_color = reloaded.life_color;
+        settings.missile_color = reloaded.missile_color;
+        settings.score_color = reloaded.score_color;
+
+        settings.normal_missile_delay = reloaded.normal_missile_delay;
+        settings.bomb_missile_delay = reloaded.bomb_missile_delay;
+        settings.homing_missile_delay = reloaded.homing_missile_delay;
+        settings.homing_turn_rate = reloaded.homing_turn_rate;
+        settings.homing_missile_lifetime = reloaded.homing_missile_lifetime;
+        settings.bomb_blast_radius = reloaded.bomb_blast_radius;
+        settings.bomb_damage = reloaded.bomb_damage;
+        settings.piercing_missile_delay = reloaded.piercing_missile_delay;
+        settings.spread_missile_delay = reloaded.spread_missile_delay;
+        settings.spread_shot_fans = reloaded.spread_shot_fans;
+        settings.points_per_weapon_level = reloaded.points_per_weapon_level;
+        settings.mine_delay = reloaded.mine_delay;
+        settings.mine_arming_delay = reloaded.mine_arming_delay;
+        settings.mine_lifetime = reloaded.mine_lifetime;
+        settings.mine_trigger_radius = reloaded.mine_trigger_radius;
+        settings.mine_blast_radius = reloaded.mine_blast_radius;
+        settings.mine_damage = reloaded.mine_damage;
+        settings.max_active_mines = reloaded.max_active_mines;
+        settings.bomb_charges = reloaded.bomb_charges;
+        settings.homing_ammo = reloaded.homing_ammo;
+        settings.mine_ammo = reloaded.mine_ammo;
+        settings.homing_reload_delay = reloaded.homing_reload_delay;
+        settings.mine_reload_delay = reloaded.mine_reload_delay;
+        settings.normal_heat_per_shot = reloaded.normal_heat_per_shot;
+        settings.heat_cooling = reloaded.heat_cooling;
+        settings.overheat_recovery = reloaded.overheat_recovery;
+        settings.laser_drain = reloaded.laser_drain;
+        settings.laser_recharge = reloaded.laser_recharge;
+        settings.laser_recovery = reloaded.laser_recovery;
+        settings.laser_damage = reloaded.laser_damage;
+        settings.laser_color = reloaded.laser_color;
+
+        settings.hit_points = reloaded.hit_points;
+        settings.destroy_points = reloaded.destroy_points;
+        settings.hits_per_multiplier = reloaded.hits_per_multiplier;
+        settings.max_score_multiplier = reloaded.max_score_multiplier;
+        settings.combo_decay_delay = reloaded.combo_decay_delay;
+
+        settings.generate_new_asteroid_after = reloaded.generate_new_asteroid_after;
+        settings.allow_inside_generation = reloaded.allow_inside_generation;
+        settings.min_generated_asteroids = reloaded.min_generated_asteroids;
+        settings.max_generated_asteroids = reloaded.max_generated_asteroids;
+        settings.min_asteroids_speed = reloaded.min_asteroids_speed;
+        settings.max_asteroids_speed = reloaded.max_asteroids_speed;
+        settings.asteroids_margin = reloaded.asteroids_margin;
+
+        return settings;
+    }
+
+    /// Checks the values the game relies on, reporting every problem at once.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut errors = vec![];
+        let mut check = |valid: bool, error: &str| {
+            if !valid {
+                errors.push(String::from(error));
+            }
+        };
+
+        check(self.window_width > 0 && self.window_height > 0, "window_width and window_height must be greater than 0");
+        check(
+            self.window_width >= MENU_ITEM_WIDTH,
+            &format!("window_width must be at least {} to fit the menus", MENU_ITEM_WIDTH),
+        );
+        check(
+            self.window_height >= MENU_ITEMS_HEIGHT,
+            &format!("window_height must be at least {} to fit the menus", MENU_ITEMS_HEIGHT),
+        );
+        check(
+            self.hud_font_point_size > 0 && (self.hud_font_point_size as u32) < MENU_ITEM_HEIGHT,
+            &format!("hud_font_point_size must be between 1 and {}", MENU_ITEM_HEIGHT - 1),
+        );
+        check(self.debug_font_point_size > 0, "debug_font_point_size must be greater than 0");
+        check(self.fps > 0, "fps must be greater than 0");
+        check(self.ticks_per_second > 0, "ticks_per_second must be greater than 0");
+        check(self.max_ticks_per_frame > 0, "max_ticks_per_frame must be greater than 0");
+        check(self.starting_generation > 0, "starting_generation must be greater than 0");
+        check(self.spaceship_height < self.window_height, "spaceship_height must be smaller than window_height");
+        check(self.spaceship_life > 0, "spaceship_life must be greater than 0");
+        check(
+            self.crash_damage_per_size >= 0.0 && self.crash_damage_per_speed >= 0.0,
+            "crash_damage_per_size and crash_damage_per_speed can't be negative",
+        );
+        check(self.crash_knockback >= 0.0, "crash_knockback can't be negative");
+        check((0.0..1.0).contains(&self.knockback_damping), "knockback_damping must be between 0 and 1 (excluded)");
+        check(self.homing_turn_rate >= 0.0, "homing_turn_rate can't be negative");
+        check(self.homing_missile_lifetime > 0, "homing_missile_lifetime must be greater than 0");
+        check(
+            self.spread_shot_fans.iter().all(|&fan| fan > 0 && fan as usize <= Spread::DIRECTIONS.len()),
+            &format!("spread_shot_fans must be between 1 and {}", Spread::DIRECTIONS.len()),
+        );
+        check(self.points_per_weapon_level > 0, "points_per_weapon_level must be greater than 0");
+        check(self.bomb_blast_radius > 0.0, "bomb_blast_radius must be greater than 0");
+        check(self.bomb_damage > 0, "bomb_damage must be greater than 0");
+        check(self.mine_arming_delay < self.mine_lifetime, "mine_arming_delay must be smaller than mine_lifetime");
+        check(self.mine_trigger_radius >= 0.0, "mine_trigger_radius can't be negative");
+        check(self.mine_blast_radius > 0.0, "mine_blast_radius must be greater than 0");
+        check(self.mine_damage > 0, "mine_damage must be greater than 0");
+        check(self.max_active_mines > 0, "max_active_mines must be greater than 0");
+        check(
+            self.bomb_charges > 0 && self.homing_ammo > 0 && self.mine_ammo > 0,
+            "bomb_charges, homing_ammo and mine_ammo must be greater than 0",
+        );
+        check(
+            self.normal_heat_per_shot >= 0.0 && self.heat_cooling >= 0.0,
+            "normal_heat_per_shot and heat_cooling can't be negative",
+        );
+        check(
+            (0.0..MAX_HEAT).contains(&self.overheat_recovery),
+            &format!("overheat_recovery must be between 0 and {} (excluded)", MAX_HEAT),
+        );
+        check(self.laser_energy > 0.0, "laser_energy must be greater than 0");
+        check(
+            self.laser_drain >= 0.0 && self.laser_recharge >= 0.0 && self.laser_damage >= 0.0,
+            "laser_drain, laser_recharge and laser_damage can't be negative",
+        );
+        check(
+            self.laser_recovery >= 0.0 && self.laser_recovery <= self.laser_energy,
+            "laser_recovery must be between 0 and laser_energy",
+        );
+        check(self.laser_width > 0, "laser_width must be greater than 0");
+        check(self.hits_per_multiplier > 0, "hits_per_multiplier must be greater than 0");
+        check(self.max_score_multiplier > 0, "max_score_multiplier must be greater than 0");
+        check(self.pixels_multiplier_factor > 0, "pixels_multiplier_factor must be greater than 0");
+        check(self.collision_grid_cell_size > 0, "collision_grid_cell_size must be greater than 0");
+        check(self.min_asteroids_size > 0, "min_asteroids_size must be greater than 0");
+        check(self.min_asteroids_size < self.max_asteroids_size, "min_asteroids_size must be smaller than max_asteroids_size");
+        check(self.min_asteroids_speed < self.max_asteroids_speed, "min_asteroids_speed must be smaller than max_asteroids_speed");
+        check(self.min_generated_asteroids > 0, "min_generated_asteroids must be greater than 0");
+        check(self.min_generated_asteroids <= self.max_generated_asteroids, "min_generated_asteroids can't be greater than max_generated_asteroids");
+        check(self.asteroids_margin.x > 0.0, "asteroids_margin.x must be greater than 0");
+        check(
+            self.asteroids_rows() > 0,
+            "window_height must fit at least one row of max_asteroids_size * pixels_multiplier_factor + asteroids_rows_padding pixels",
+        );
+
+        if errors.is_empty() {
+            return Ok(());
+        }
+
+        return Err(errors.join("\n"));
+    }
+
+    pub fn parse(source: &str) -> Result<Settings, String> {
+        let settings: Settings = ron::from_str(source).map_err(|error| error.to_string())?;
+        settings.validate()?;
+
+        return Ok(settings);
+    }
+
+    /// Falls back to the default settings when the file doesn't exist.
+    pub fn load(path: &str) -> Result<Settings, String> {
+        if !Path::new(path).exists() {
+            return Ok(Settings::default());
+        }
+
+        return Settings::load_file(path);
+    }
+
+    /// Loads an existing file, a missing one is an error.
+    pub fn load_file(path: &str) -> Result<Settings, String> {
+        let source = fs::read_to_string(path)
+            .map_err(|error| format!("Failed to read {}: {}", path, error))?;
+
+        return Settings::parse(&source).map_err(|error| format!("Invalid settings in {}:\n{}", path, error));
+    }
+}
+
+static SETTINGS: OnceLock<RwLock<Arc<Settings>>> = OnceLock::new();
+
+fn settings_lock() -> &'static RwLock<Arc<Settings>> {
+    return SETTINGS.get_or_init(|| RwLock::new(Arc::new(Settings::default())));
+}
+
+/// The settings currently in use, the defaults until `set` is called.
+pub fn get() -> Arc<Settings> {
+    return settings_lock().read().unwrap().clone();
+}
+
+pub fn set(settings: Settings) {
+    *settings_lock().write().unwrap() = Arc::new(settings);
+}
+
+/// Polls a settings file so edits can be applied while the game runs.
+pub struct Watcher {
+    path: String,
+    last_modified: Option<SystemTime>,
+}
+
+impl Watcher {
+    pub fn new(path: &str) -> Watcher {
+        return Watcher {
+            path: String::from(path),
+            last_modified: Watcher::modified(path),
+        };
+    }
+
+    fn modified(path: &str) -> Option<SystemTime> {
+        return fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+    }
+
+    /// Loads the file again if it changed since the last call.
+    ///
+    /// A file that went missing is an error rather than the default settings, editors saving
+    /// by replacing the file may remove it for a moment.
+    pub fn poll(&mut self) -> Option<Result<Settings, String>> {
+        let modified = Watcher::modified(&self.path);
+        if modified == self.last_modified {
+            return None;
+        }
+        self.last_modified = modified;
+
+        if modified.is_none() {
+            return Some(Err(format!("Settings file not found: {}, the current settings are kept", self.path)));
+        }
+
+        return Some(Settings::load_file(&self.path));
+    }
+}
@@ -0,0 +1,40 @@
+use std::fs;
+
+use serde::{Serialize, Deserialize};
+
+use crate::game::Game;
+
+/// The whole state of a game, its RNG included, saved as RON.
+#[derive(Serialize, Deserialize)]
+pub struct Snapshot {
+    pub game: Game,
+}
+
+impl Snapshot {
+    /// Copies the game, which carries on untouched.
+    pub fn take(game: &Game) -> Snapshot {
+        return Snapshot {
+            game: game.clone(),
+        };
+    }
+
+    pub fn restore(self) -> Game {
+        return self.game;
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let serialized = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|error| format!("Failed to serialize the snapshot: {}", error))?;
+
+        return fs::write(path, serialized)
+            .map_err(|error| format!("Failed to write {}: {}", path, error));
+    }
+
+    pub fn load(path: &str) -> Result<Snapshot, String> {
+        let source = fs::read_to_string(path)
+            .map_err(|error| format!("Failed to read {}: {}", path, error))?;
+
+        return ron::from_str(&source)
+            .map_err(|error| format!("Invalid snapshot {}: {}", path, error));
+    }
+}
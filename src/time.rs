@@ -1,5 +1,7 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::settings;
+
 pub fn now() -> u128 {
     let time = SystemTime::now();
     let now_nano = time
@@ -11,4 +13,35 @@ pub fn now() -> u128 {
 
 pub const fn to_nano(milliseconds: u16) -> u128 {
     return milliseconds as u128 * 1_000_000;
-}
\ No newline at end of file
+}
+
+/// Converts a duration to the closest amount of simulation ticks.
+pub fn to_ticks(milliseconds: u16) -> u64 {
+    return (milliseconds as u64 * settings::get().ticks_per_second as u64 + 500) / 1000;
+}
+
+pub fn tick_duration() -> u128 {
+    return 1_000_000_000 / settings::get().ticks_per_second as u128;
+}
+
+/// Formats a unix timestamp (in seconds) as an UTC `YYYY-MM-DD` date.
+pub fn format_date(timestamp: u64) -> String {
+    // Civil from days, see http://howardhinnant.github.io/date_algorithms.html
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    return format!("{:04}-{:02}-{:02}", year, month, day);
+}
+
+/// Formats an amount of seconds as `MM:SS`.
+pub fn format_duration(seconds: u64) -> String {
+    return format!("{:02}:{:02}", seconds / 60, seconds % 60);
+}
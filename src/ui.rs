@@ -5,23 +5,28 @@ use sdl2::event::{Event};
 use sdl2::render;
 use sdl2::image::{InitFlag, LoadTexture};
 use sdl2::pixels::Color;
-use sdl2::render::Texture;
+use sdl2::render::{Texture, BlendMode};
 use sdl2::keyboard::Keycode;
+use sdl2::mouse::MouseButton;
 
 use crate::time;
 
-use sdl2::rect::Rect;
+use sdl2::rect::{Point, Rect};
 use sdl2::ttf::Font;
 
 use std::sync::mpsc::{Sender, Receiver};
 use std::path::Path;
 
 use crate::helper::{G2UMessage, U2GMessage};
+use crate::physics;
 use crate::physics::{Position};
 use crate::asteroid::Asteroid;
 use crate::settings;
 use crate::rectangle::{Rectangle, Size, RectangleSize};
 use crate::game;
+use crate::missile::missiles::{KINDS, Normal, Bomb, Homing, Piercing, Spread, Mine};
+use crate::ammo::MAX_HEAT;
+use crate::scene::Scene;
 
 use game::{Game};
 
@@ -31,6 +36,7 @@ pub struct UiSettings {
     pub title: String,
     pub width: u32,
     pub height: u32,
+    pub fullscreen: bool,
 }
 
 pub struct Ui {
@@ -46,11 +52,12 @@ impl Ui {
         let video_subsystem = sdl_context.video().unwrap();
         let _image_context = sdl2::image::init(InitFlag::PNG);
 
-        let window = video_subsystem
-            .window(&ui_settings.title, ui_settings.width, ui_settings.height)
-            .position_centered()
-            .build()
-            .unwrap();
+        let mut window_builder = video_subsystem.window(&ui_settings.title, ui_settings.width, ui_settings.height);
+        window_builder.position_centered();
+        if ui_settings.fullscreen {
+            window_builder.fullscreen();
+        }
+        let window = window_builder.build().unwrap();
 
         let canvas = window.into_canvas().build().unwrap();
 
@@ -65,20 +72,27 @@ impl Ui {
     }
 
     pub fn to_pixels(value: u32) -> u32 {
-        return value * settings::PIXELS_MULTIPLIER_FACTOR;
+        let settings = settings::get();
+        return value * settings.pixels_multiplier_factor;
+    }
+
+    /// Rounds a simulation position to the pixel it's drawn at.
+    pub fn to_screen(position: Position) -> Point {
+        return Point::new(position.x.round() as i32, position.y.round() as i32);
     }
 
     fn draw_background(&mut self, sprites_texture: &Texture) {
+        let settings = settings::get();
         self.draw_sprite(sprites_texture,
-            settings::BACKGROUND_SPRITE_RECTANGLE,
+            settings.background_sprite_rectangle.clone(),
             Rectangle {
                 position: Position {
-                    x: settings::WINDOW_WIDTH as i32 / 2,
-                    y: settings::WINDOW_HEIGHT as i32 / 2,
+                    x: settings.window_width as f32 / 2.0,
+                    y: settings.window_height as f32 / 2.0,
                 },
                 size: Size::Rectangle(RectangleSize {
-                    width: settings::WINDOW_WIDTH,
-                    height: settings::WINDOW_HEIGHT,
+                    width: settings.window_width,
+                    height: settings.window_height,
                 })
             }
         );
@@ -87,26 +101,28 @@ impl Ui {
     fn draw_asteroids(
         &mut self,
         game: &mut Game,
+        interpolation: f32,
         sprites_texture: &Texture,
     ) {
+        let settings = settings::get();
         for row in game.asteroids.iter() {
             for asteroid in row.iter() {
                 let size = Ui::to_pixels(asteroid.size as u32);
                 
                 let target_rectangle = Rectangle {
-                    position: asteroid.position,
+                    position: physics::interpolate(asteroid.previous_position, asteroid.position, interpolation),
                     size: Size::Square(size),
                 };
 
                 let target_rectangle_corners = target_rectangle.get_corners();
 
-                if target_rectangle_corners.top_left.x > settings::WINDOW_WIDTH as i32 {
+                if target_rectangle_corners.top_left.x > settings.window_width as f32 {
                     continue;
                 }
 
                 self.draw_sprite(
                     sprites_texture,
-                    settings::ASTEROID_SPRITE_RECTANGLE,
+                    settings.asteroid_sprite_rectangle.clone(),
                     target_rectangle,
                 );
             }
@@ -118,80 +134,310 @@ impl Ui {
         game: &mut Game,
         sprites_texture: &Texture,
     ) {        
+        let settings = settings::get();
+        // Blinks while it's invulnerable
+        if game.spaceship.is_invulnerable(game.current_tick) && (game.current_tick / 4).is_multiple_of(2) {
+            return;
+        }
+
         self.draw_sprite(
             sprites_texture,
-            settings::SPACESHIP_SPRITE_RECTANGLE,
+            settings.spaceship_sprite_rectangle.clone(),
             Rectangle {
                 position: Position {
                     x: game.spaceship.position.x, 
-                    y: game.spaceship.position.y + settings::SPACESHIP_HEIGHT as i32 / 2,
+                    y: game.spaceship.position.y + settings.spaceship_height as f32 / 2.0,
                 },
                 size: Size::Rectangle(RectangleSize {
-                    width: settings::SPACESHIP_WIDTH,
-                    height: settings::SPACESHIP_HEIGHT,
+                    width: settings.spaceship_width,
+                    height: settings.spaceship_height,
                 })
             },
         );            
     }
 
-    fn draw_missiles(&mut self, game: &mut Game, sprites_texture: &Texture) {
+    fn draw_missiles(&mut self, game: &mut Game, interpolation: f32, sprites_texture: &mut Texture) {
+        let settings = settings::get();
         for missile in game.missiles.iter() {
-            if missile.position.x > settings::WINDOW_WIDTH as i32 || !missile.active { continue; }
+            if missile.position.x > settings.window_width as f32 || !missile.active { continue; }
             let rect = Rectangle {
-                position: missile.position,
+                position: physics::interpolate(missile.previous_position, missile.position, interpolation),
                 size: Size::Rectangle(RectangleSize {
-                    width: settings::MISSILE_WIDTH,
-                    height: settings::MISSILE_HEIGHT,
+                    width: settings.missile_width,
+                    height: settings.missile_height,
                 }),
             };
+            let tint = missile.missile_type.kind().render_hint().tint;
+            sprites_texture.set_color_mod(tint.r, tint.g, tint.b);
             self.draw_sprite(
                 sprites_texture,
-                settings::MISSILE_SPRITE_RECTANGLE,
+                settings.missile_sprite_rectangle.clone(),
                 rect,
             );
         }
+        sprites_texture.set_color_mod(255, 255, 255);
+
+        if let Some(beam) = game.spaceship.laser.beam {
+            let beam_rectangle = beam.rectangle();
+            let beam_size = Rectangle::to_rectangle_size(beam_rectangle.size.clone());
+            let beam_top_left = Ui::to_screen(beam_rectangle.get_corners().top_left);
+
+            self.canvas.set_draw_color(settings.laser_color);
+            self.canvas.fill_rect(Rect::new(
+                beam_top_left.x(),
+                beam_top_left.y(),
+                beam_size.width,
+                beam_size.height,
+            )).unwrap();
+        }
     }
 
     fn draw_spaceship_life(&mut self, game: &mut Game) {
+        let settings = settings::get();
         let canvas = &mut self.canvas;
 
         let size = RectangleSize{ 
             width: 13,
-            height: (settings::SPACESHIP_HEIGHT as f32 / settings::SPACESHIP_LIFE as f32 * game.spaceship.life as f32) as u32,
+            height: (settings.spaceship_height as f32 / settings.spaceship_life as f32 * game.spaceship.life as f32) as u32,
         };
 
         let life_rectangle = Rectangle {
             position: Position {
                 x: game.spaceship.position.x
-                    - settings::SPACESHIP_WIDTH as i32 / 2
-                    - size.width as i32 - 3,
-                y: game.spaceship.position.y + (settings::SPACESHIP_HEIGHT - size.height) as i32,
+                    - settings.spaceship_width as f32 / 2.0
+                    - size.width as f32 - 3.0,
+                y: game.spaceship.position.y + (settings.spaceship_height - size.height) as f32,
             },
             size: Size::Rectangle(size)
         };
+        let life_point = Ui::to_screen(life_rectangle.position);
 
         if game.debugging && game.debug_options.invincible  {
-            canvas.set_draw_color(settings::DEBUG_COLOR);
+            canvas.set_draw_color(settings.debug_color);
         } else {
-            canvas.set_draw_color(settings::LIFE_COLOR);
+            canvas.set_draw_color(settings.life_color);
         }
         canvas.fill_rect(Rect::new(
-            life_rectangle.position.x,
-            life_rectangle.position.y,
+            life_point.x(),
+            life_point.y(),
+            size.width,
+            size.height,
+        )).unwrap();
+    }
+
+    /// Shown on the left of the life, full when the laser is fully charged.
+    fn draw_laser_energy(&mut self, game: &mut Game) {
+        let settings = settings::get();
+        let size = RectangleSize {
+            width: 6,
+            height: (settings.spaceship_height as f32 * game.spaceship.laser.energy / settings.laser_energy) as u32,
+        };
+        // Next to the life bar, which is 13 pixels wide
+        let energy_position = Ui::to_screen(Position {
+            x: game.spaceship.position.x
+                - settings.spaceship_width as f32 / 2.0
+                - 13.0 - 3.0
+                - size.width as f32 - 3.0,
+            y: game.spaceship.position.y + (settings.spaceship_height - size.height) as f32,
+        });
+
+        self.canvas.set_draw_color(settings.laser_color);
+        self.canvas.fill_rect(Rect::new(
+            energy_position.x(),
+            energy_position.y(),
             size.width,
             size.height,
         )).unwrap();
     }
 
-    fn inputs(&mut self, tx: &Sender<U2GMessage>) {
+    fn draw_score(&mut self, game: &mut Game, hud_font: &Font, texture_creator: &TextureCreator) {
+        let settings = settings::get();
+        let heat = if game.spaceship.heat.overheated {
+            String::from("OVERHEATED")
+        } else {
+            format!("{:.0}%", game.spaceship.heat.value / MAX_HEAT * 100.0)
+        };
+        let mut score_text = format!(
+"Score: {points}
+Combo: x{multiplier}
+Weapon level: {weapon_level}
+Heat: {heat}
+",
+points=game.score.points,
+multiplier=game.score.multiplier,
+weapon_level=game.spaceship.weapon_level,
+);
+        for kind in KINDS.iter() {
+            if let Some((rounds, capacity)) = game.spaceship.magazine.rounds(kind.id()) {
+                score_text += &format!("{}: {rounds}/{capacity}\n", kind.display_name());
+            }
+        }
+
+        self.write_text(
+            &score_text,
+            settings.score_color,
+            settings.score_position(),
+            hud_font,
+            texture_creator,
+            Some(settings.hud_font_point_size + 5),
+        );
+    }
+
+    fn draw_high_scores(&mut self, game: &mut Game, mut y: i32, hud_font: &Font, texture_creator: &TextureCreator) {
+        let settings = settings::get();
+        for (rank, high_score) in game.high_scores.entries.iter().enumerate() {
+            let color = if game.high_score_rank == Some(rank) {
+                settings.menu_highlight_color
+            } else {
+                settings.menu_color
+            };
+
+            let high_score_text = format!(
+                "{rank:>2}. {score}   - Gen {generation}   - {duration}   - {date}   - Seed {seed}",
+                rank=rank + 1,
+                score=high_score.score,
+                generation=high_score.generation,
+                duration=time::format_duration(high_score.duration),
+                date=time::format_date(high_score.date),
+                seed=high_score.seed,
+            );
+            self.write_text(
+                &high_score_text,
+                color,
+                Point::new(settings.menu_text_x(), y),
+                hud_font,
+                texture_creator,
+                None,
+            );
+            y += settings.menu_line_height() as i32;
+        }
+    }
+
+    fn draw_settings_error(&mut self, game: &mut Game, debug_font: &Font, texture_creator: &TextureCreator) {
+        let settings = settings::get();
+        if let Some(settings_error) = &game.settings_error {
+            let text = format!("Settings not reloaded:\n{}", settings_error);
+            let lines_count = text.lines().count() as i32;
+
+            self.write_text(
+                &text,
+                settings.debug_color,
+                Point::new(10, settings.window_height as i32 - 10 - lines_count * 15),
+                debug_font,
+                texture_creator,
+                None,
+            );
+        }
+    }
+
+    pub fn menu_item_rect(item_index: usize) -> Rect {
+        let settings = settings::get();
+        return Rect::new(
+            (settings.window_width as i32 - settings::MENU_ITEM_WIDTH as i32) / 2,
+            settings.menu_items_y() + (settings::MENU_ITEM_HEIGHT as i32 + 10) * item_index as i32,
+            settings::MENU_ITEM_WIDTH,
+            settings::MENU_ITEM_HEIGHT,
+        );
+    }
+
+    fn menu_item_at(scene: Scene, x: i32, y: i32) -> Option<usize> {
+        return (0..scene.menu_items().len())
+            .find(|&item_index| Ui::menu_item_rect(item_index).contains_point((x, y)));
+    }
+
+    fn draw_menu(&mut self, game: &mut Game, hud_font: &Font, texture_creator: &TextureCreator) {
+        let settings = settings::get();
+        let scene = game.scenes.current();
+
+        if scene.is_overlay() {
+            self.canvas.set_blend_mode(BlendMode::Blend);
+            self.canvas.set_draw_color(settings.menu_background_color);
+            self.canvas.fill_rect(None).unwrap();
+            self.canvas.set_blend_mode(BlendMode::None);
+        }
+
+        let mut y = settings.menu_title_y();
+        self.write_text(
+            scene.title(),
+            settings.menu_color,
+            Point::new(settings.menu_text_x(), y),
+            hud_font,
+            texture_creator,
+            None,
+        );
+        y += settings.menu_line_height() as i32 * 2;
+
+        match scene {
+            Scene::GameOver => {
+                let summary_text = format!(
+"Score: {points}   - Generation: {generation}   - Time: {duration}
+High scores:
+",
+points=game.score.points,
+generation=game.generation,
+duration=time::format_duration(game.current_tick / settings.ticks_per_second as u64),
+);
+                self.write_text(
+                    &summary_text,
+                    settings.menu_color,
+                    Point::new(settings.menu_text_x(), y),
+                    hud_font,
+                    texture_creator,
+                    Some(settings.menu_line_height()),
+                );
+                y += settings.menu_line_height() as i32 * 2;
+
+                self.draw_high_scores(game, y, hud_font, texture_creator);
+            }
+            Scene::HighScores => {
+                self.draw_high_scores(game, y, hud_font, texture_creator);
+            }
+            _ => {}
+        }
+
+        for (item_index, item) in scene.menu_items().iter().enumerate() {
+            let rect = Ui::menu_item_rect(item_index);
+
+            let color = if item_index == game.scenes.selected_item {
+                self.canvas.set_draw_color(settings.menu_highlight_color);
+                self.canvas.draw_rect(rect).unwrap();
+                settings.menu_highlight_color
+            } else {
+                settings.menu_color
+            };
+
+            self.write_text(
+                &item.label(game),
+                color,
+                Point::new(
+                    rect.x() + 15,
+                    rect.y() + (settings::MENU_ITEM_HEIGHT as i32 - settings.hud_font_point_size as i32) / 2,
+                ),
+                hud_font,
+                texture_creator,
+                None,
+            );
+        }
+    }
+
+    fn inputs(&mut self, tx: &Sender<U2GMessage>, scene: Scene) {
         for event in self.event_pump.poll_iter() {
             match event {
                 Event::MouseMotion { x, y, ..} => {
                     let mouse_position = Position {
-                        x: x,
-                        y: y,
+                        x: x as f32,
+                        y: y as f32,
                     };
                     tx.send(U2GMessage::MouseMotion(mouse_position)).unwrap();
+
+                    if let Some(item_index) = Ui::menu_item_at(scene, x, y) {
+                        tx.send(U2GMessage::MenuHover(item_index)).unwrap();
+                    }
+                }
+                Event::MouseButtonDown { mouse_btn: MouseButton::Left, x, y, .. } if scene != Scene::InGame => {
+                    if let Some(item_index) = Ui::menu_item_at(scene, x, y) {
+                        tx.send(U2GMessage::MenuClick(item_index)).unwrap();
+                    }
                 }
                 Event::KeyDown {
                     keycode: Some(Keycode::H),
@@ -199,9 +445,19 @@ impl Ui {
                 } => {
                     println!(
 "Key binds:
-    - Mouse's Left Button: Shoot
-    - Mouse's Right Button: Stop shooting
-    - Escape: Pause the game
+    - Mouse's Left Button (hold): Shoot normal missiles / Select in the menus
+    - Mouse's Right Button (hold): Shoot bombs
+    - Mouse's Middle Button (hold): Shoot homing missiles
+    - Z (hold): Shoot piercing rounds
+    - X (hold): Shoot a spread shot
+    - C (hold): Drop mines
+    - Space (hold): Fire the laser
+    - Escape: Pause the game / Go back in the menus
+    - Up, Down and Enter: Navigate the menus
+    - R: Restart (on the game over screen)
+    - Q: Quit (on the game over screen)
+    - F2: Quick save
+    - F3: Quick load
 
 Debugging:
     - F5: Toggle debug mode
@@ -221,17 +477,56 @@ Debugging:
                     tx.send(U2GMessage::Close).unwrap();
                 }
                 _ => {
-                    tx.send(U2GMessage::Event(event)).unwrap();
+                    if let Some(message) = Ui::to_game_message(&event) {
+                        tx.send(message).unwrap();
+                    }
                 }
             }
         }
     }
 
+    fn to_game_message(event: &Event) -> Option<U2GMessage> {
+        let message = match event {
+            Event::MouseButtonDown { mouse_btn: MouseButton::Left, .. } => U2GMessage::StartShooting(Normal::ID),
+            Event::MouseButtonUp { mouse_btn: MouseButton::Left, .. } => U2GMessage::StopShooting(Normal::ID),
+            Event::MouseButtonDown { mouse_btn: MouseButton::Right, .. } => U2GMessage::StartShooting(Bomb::ID),
+            Event::MouseButtonUp { mouse_btn: MouseButton::Right, .. } => U2GMessage::StopShooting(Bomb::ID),
+            Event::MouseButtonDown { mouse_btn: MouseButton::Middle, .. } => U2GMessage::StartShooting(Homing::ID),
+            Event::MouseButtonUp { mouse_btn: MouseButton::Middle, .. } => U2GMessage::StopShooting(Homing::ID),
+            Event::KeyDown { keycode: Some(Keycode::Z), .. } => U2GMessage::StartShooting(Piercing::ID),
+            Event::KeyUp { keycode: Some(Keycode::Z), .. } => U2GMessage::StopShooting(Piercing::ID),
+            Event::KeyDown { keycode: Some(Keycode::X), .. } => U2GMessage::StartShooting(Spread::ID),
+            Event::KeyUp { keycode: Some(Keycode::X), .. } => U2GMessage::StopShooting(Spread::ID),
+            Event::KeyDown { keycode: Some(Keycode::C), .. } => U2GMessage::StartShooting(Mine::ID),
+            Event::KeyUp { keycode: Some(Keycode::C), .. } => U2GMessage::StopShooting(Mine::ID),
+            Event::KeyDown { keycode: Some(Keycode::Space), .. } => U2GMessage::StartLaser,
+            Event::KeyUp { keycode: Some(Keycode::Space), .. } => U2GMessage::StopLaser,
+            Event::KeyDown { keycode: Some(Keycode::Escape), .. } => U2GMessage::Back,
+            Event::KeyDown { keycode: Some(Keycode::Up), .. } => U2GMessage::MenuUp,
+            Event::KeyDown { keycode: Some(Keycode::Down), .. } => U2GMessage::MenuDown,
+            Event::KeyDown { keycode: Some(Keycode::Return), .. } => U2GMessage::MenuSelect,
+            Event::KeyDown { keycode: Some(Keycode::R), .. } => U2GMessage::Restart,
+            Event::KeyDown { keycode: Some(Keycode::Q), .. } => U2GMessage::Quit,
+            Event::KeyDown { keycode: Some(Keycode::F2), .. } => U2GMessage::QuickSave,
+            Event::KeyDown { keycode: Some(Keycode::F3), .. } => U2GMessage::QuickLoad,
+            Event::KeyDown { keycode: Some(Keycode::F5), .. } => U2GMessage::ToggleDebugging,
+            Event::KeyDown { keycode: Some(Keycode::F6), .. } => U2GMessage::ToggleGenerationLine,
+            Event::KeyDown { keycode: Some(Keycode::F7), .. } => U2GMessage::ToggleRows,
+            Event::KeyDown { keycode: Some(Keycode::F8), .. } => U2GMessage::ToggleGameState,
+            Event::KeyDown { keycode: Some(Keycode::F9), .. } => U2GMessage::ToggleInvincible,
+            Event::KeyDown { keycode: Some(Keycode::F10), .. } => U2GMessage::ToggleAsteroidGeneration,
+
+            _ => return None,
+        };
+
+        return Some(message);
+    }
+
     fn write_text(
         &mut self,
         text: &str,
         color: Color,
-        position: Position,
+        position: Point,
         font: &Font,
         texture_creator: &TextureCreator,
         line_height: Option<u16>,
@@ -250,7 +545,7 @@ Debugging:
 
             let render::TextureQuery { width, height, .. } = texture.query();
         
-            let target = Rect::new(position.x, position.y + line_height as i32 * line_i as i32, width, height);
+            let target = Rect::new(position.x(), position.y() + line_height as i32 * line_i as i32, width, height);
             self.canvas.copy(&texture, None, Some(target)).unwrap();
 
         }
@@ -266,23 +561,23 @@ Debugging:
     ) {
         let sprite_rectangle_size = Rectangle::to_rectangle_size(sprite_rectangle.size);
         
-        let target_corners = target_rectangle.get_corners();
+        let target_top_left = Ui::to_screen(target_rectangle.get_corners().top_left);
         let target_rectangle_size = Rectangle::to_rectangle_size(target_rectangle.size);
 
         self.canvas.copy(
             texture,
             Some(
                 Rect::new(
-                    sprite_rectangle.position.x,
-                    sprite_rectangle.position.y,
+                    sprite_rectangle.position.x as i32,
+                    sprite_rectangle.position.y as i32,
                     sprite_rectangle_size.width,
                     sprite_rectangle_size.height,
                 )
             ),
             Some(
                 Rect::new(
-                    target_corners.top_left.x,
-                    target_corners.top_left.y,
+                    target_top_left.x(),
+                    target_top_left.y(),
                     target_rectangle_size.width,
                     target_rectangle_size.height,
                 )
@@ -296,31 +591,47 @@ Debugging:
     }
 
     pub fn run(&mut self, tx: &Sender<U2GMessage>, rx: &Receiver<G2UMessage>) {
+        let settings = settings::get();
         let ttf_context = sdl2::ttf::init().unwrap();
         let texture_creator = self.canvas.texture_creator();
 
         // Load Sprites
-        let sprites_texture = texture_creator.load_texture(
-            Path::new(settings::SPRITES_FILE_PATH)
+        let mut sprites_texture = texture_creator.load_texture(
+            Path::new(&settings.sprites_file_path)
         ).unwrap();
 
         // Load debug font
         let mut debug_font = ttf_context.load_font(
-            Path::new(settings::DEBUG_FONT_PATH),
-            settings::DEBUG_FONT_POINT_SIZE
+            Path::new(&settings.debug_font_path),
+            settings.debug_font_point_size
         ).unwrap();
         debug_font.set_style(sdl2::ttf::FontStyle::NORMAL);
 
+        // Load HUD font
+        let hud_font = ttf_context.load_font(
+            Path::new(&settings.hud_font_path),
+            settings.hud_font_point_size
+        ).unwrap();
+
         let mut last_frame_timestamp: u128 = time::now();
 
         for message in rx.iter() {
-            self.inputs(tx);
-
-            let G2UMessage::StateUpdate(mut game) = message;
+            let G2UMessage::StateUpdate(mut game, interpolation) = message;
             let game = &mut game;
+            let scene = game.scenes.current();
+
+            self.inputs(tx, scene);
 
             self.draw_background(&sprites_texture);
 
+            if !scene.is_overlay() {
+                self.draw_menu(game, &hud_font, &texture_creator);
+                self.draw_settings_error(game, &debug_font, &texture_creator);
+                self.canvas.present();
+                last_frame_timestamp = time::now();
+                continue;
+            }
+
             if game.debugging {
                 self.debug(
                     game,
@@ -331,10 +642,17 @@ Debugging:
             }
 
             self.draw_spaceship(game, &sprites_texture);
-            self.draw_missiles(game, &sprites_texture);
-            self.draw_asteroids(game, &sprites_texture);
+            self.draw_missiles(game, interpolation, &mut sprites_texture);
+            self.draw_asteroids(game, interpolation, &sprites_texture);
             self.draw_spaceship_life(game);
+            self.draw_laser_energy(game);
+            self.draw_score(game, &hud_font, &texture_creator);
 
+            if scene != Scene::InGame {
+                self.draw_menu(game, &hud_font, &texture_creator);
+            }
+
+            self.draw_settings_error(game, &debug_font, &texture_creator);
             self.canvas.present();
             last_frame_timestamp = time::now();
         }
@@ -347,6 +665,7 @@ Debugging:
         debug_font: &Font,
         texture_creator: &TextureCreator,
     ) {
+        let settings = settings::get();
         let canvas = &mut self.canvas;
 
         if game.debug_options.generation_line {
@@ -354,20 +673,32 @@ Debugging:
             if Asteroid::appearing_asteroids(&game.asteroids) > 0 {
                 canvas.set_draw_color(Color::CYAN);
             } else {
-                canvas.set_draw_color(settings::DEBUG_COLOR);
+                canvas.set_draw_color(settings.debug_color);
             }
             
             canvas.fill_rect(
                 Rect::new(
-                    settings::WINDOW_WIDTH as i32
-                    - settings::GENERATE_NEW_ASTEROID_AFTER as i32,
+                    settings.window_width as i32
+                    - settings.generate_new_asteroid_after as i32,
                     0,
                     1,
-                    settings::WINDOW_HEIGHT
+                    settings.window_height
                 )
             ).unwrap();
         }
 
+        // Explosions radius
+        canvas.set_draw_color(settings.debug_color);
+        for explosion in game.explosions.iter() {
+            let circle: Vec<Point> = (0..=32)
+                .map(|point_i| {
+                    let angle = point_i as f32 / 32.0 * std::f32::consts::TAU;
+                    return Ui::to_screen(explosion.center + Position::new(angle.cos(), angle.sin()) * explosion.radius);
+                })
+                .collect();
+            canvas.draw_lines(circle.as_slice()).unwrap();
+        }
+
         if game.debug_options.rows {
             let mut rects: Vec<Rect> = vec![];
             let mut rects_with_missiles: Vec<Rect> = vec![];
@@ -384,9 +715,9 @@ Debugging:
                 let y_position = Game::get_centered_row_y_position(row);
                 let row_rect = Rect::new(
                     0,
-                    y_position as i32 - settings::ASTEROIDS_ROWS_HEIGHT as i32 / 2,
-                    settings::WINDOW_WIDTH,
-                    settings::ASTEROIDS_ROWS_HEIGHT - 2
+                    y_position as i32 - settings.asteroids_rows_height() as i32 / 2,
+                    settings.window_width,
+                    settings.asteroids_rows_height() - 2
                 );
 
                 let mut contains_missiles = false;
@@ -403,9 +734,9 @@ Debugging:
                 }
             }
 
-            canvas.set_draw_color(settings::DEBUG_COLOR);
+            canvas.set_draw_color(settings.debug_color);
             canvas.draw_rects(&rects).unwrap();
-            canvas.set_draw_color(settings::MISSILE_COLOR);
+            canvas.set_draw_color(settings.missile_color);
             canvas.draw_rects(&rects_with_missiles).unwrap();
         }
 
@@ -422,6 +753,7 @@ Debugging:
 "Missiles count: {missile_count}   - Asteroids generation: {asteroids_generation}
 Asteroids count: {asteroids_count} - Invincible: {invincible}
 Life: {life}        - FPS: {fps}
+Seed: {seed}        - Tick: {tick}
 ",
 missile_count=missiles,
 asteroids_count=asteroids,
@@ -429,15 +761,14 @@ life=game.spaceship.life,
 asteroids_generation=game.debug_options.asteroid_generation,
 invincible=game.debug_options.invincible,
 fps=fps,
+seed=game.seed,
+tick=game.current_tick,
 );
 
             self.write_text(
                 &info_text,
-                settings::DEBUG_COLOR,
-                Position {
-                    x: 10,
-                    y: 10,
-                }, 
+                settings.debug_color,
+                Point::new(10, 10),
                 debug_font,
                 texture_creator,
                 None,